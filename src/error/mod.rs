@@ -4,7 +4,7 @@ use std::fmt;
 use std::error::Error as StdError;
 
 /// The possible errors that can occur during barcode encoding and generation.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Error {
   /// An invalid character found during encoding.
   Character,
@@ -12,27 +12,78 @@ pub enum Error {
   Length,
   /// An error during barcode generation.
   Generate,
+  /// An unknown barcode symbology.
+  Symbology,
+  /// A check digit in the data that does not match the calculated check digit.
+  Checksum,
+  /// A wide-to-narrow ratio outside of the range allowed by the symbology.
+  Ratio,
+  /// A gap between a barcode and its add-on outside of the range allowed by the symbology.
+  Gap,
+  /// An invalid GS1 element string, along with the digits of the Application Identifier that
+  /// failed.
+  GS1(String, GS1Error),
+}
+
+/// The reasons a GS1 element string can be rejected.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GS1Error {
+  /// The Application Identifier is unknown.
+  Unknown,
+  /// The data length is invalid for the Application Identifier.
+  Length,
+  /// The data contains a character that is invalid for the Application Identifier.
+  Character,
+  /// The data has an incorrect check digit.
+  CheckDigit,
 }
 
 /// Alias-type for Result<T, barcoders::error::Error>.
 pub type Result<T> = ::std::result::Result<T, Error>;
 
+impl Error {
+    fn message(&self) -> &'static str {
+        match *self {
+            Error::Character => "Barcode data is invalid",
+            Error::Length => "Barcode data length is invalid",
+            Error::Generate => "Could not generate barcode data",
+            Error::Symbology => "Barcode symbology is unknown",
+            Error::Checksum => "Barcode check digit is incorrect",
+            Error::Ratio => "Barcode wide/narrow ratio is invalid",
+            Error::Gap => "Barcode add-on gap is invalid",
+            Error::GS1(..) => "GS1 element string is invalid",
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(self.description())
+        match *self {
+            Error::GS1(ref ai, e) => write!(f, "GS1 element string ({}) is invalid: {}", ai, e),
+            _ => f.write_str(self.message()),
+        }
+    }
+}
+
+impl fmt::Display for GS1Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match *self {
+            GS1Error::Unknown => "unknown application identifier",
+            GS1Error::Length => "data length is invalid",
+            GS1Error::Character => "data contains an invalid character",
+            GS1Error::CheckDigit => "check digit is incorrect",
+        };
+
+        f.write_str(description)
     }
 }
 
 impl StdError for Error {
     fn description(&self) -> &str {
-        match *self {
-            Error::Character => "Barcode data is invalid",
-            Error::Length => "Barcode data length is invalid",
-            Error::Generate => "Could not generate barcode data",
-        }
+        self.message()
     }
 
-    fn cause(&self) -> Option<&StdError> {
+    fn cause(&self) -> Option<&dyn StdError> {
         None
     }
 }
@@ -4,9 +4,13 @@
 //!
 //! You will pretty much never need to turn this feature on unless you are adding new functionality
 //! or running the test suite.
+//!
+//! Barcodes with more than one row, such as 4-state postal barcodes, are generated from their
+//! `Layout` via `generate_layout`, showing the ascenders, tracker and descenders as separate rows.
 
 use std::iter::repeat;
 use error::Result;
+use sym::layout::Layout;
 
 /// The ASCII barcode generator type.
 #[derive(Copy, Clone, Debug)]
@@ -23,6 +27,7 @@ const CHARS: [char; 2] = [' ', '#'];
 
 impl ASCII {
     /// Returns a new ASCII with default values.
+    #[allow(clippy::new_without_default)]
     pub fn new() -> ASCII {
         ASCII {
             height: 10,
@@ -38,18 +43,21 @@ impl ASCII {
 
     /// Generates the given barcode. Returns a `Result<String, Error>` indicating success.
     pub fn generate<T: AsRef<[u8]>>(&self, barcode: T) -> Result<String> {
-        let mut output = String::new();
-        let row = self.generate_row(barcode.as_ref());
-
-        for (i, _l) in (0..self.height).enumerate() {
-            output.push_str(&row[..]);
-
-            if i < self.height - 1 {
-                output.push_str("\n");
-            }
-        }
+        self.generate_layout(&Layout::from(barcode.as_ref()))
+    }
 
-        Ok(output)
+    /// Generates the given barcode layout, scaling the rows to the height of the ASCII output.
+    /// Returns a `Result<String, Error>` indicating success.
+    pub fn generate_layout(&self, layout: &Layout) -> Result<String> {
+        let lines: Vec<String> = layout.rows
+            .iter()
+            .zip(layout.scale(self.height as u32))
+            .flat_map(|(row, (_, height))| {
+                repeat(self.generate_row(&row.modules[..])).take(height as usize)
+            })
+            .collect();
+
+        Ok(lines.join("\n"))
     }
 }
 
@@ -64,6 +72,7 @@ mod tests {
     use ::sym::code128::*;
     use ::sym::tf::*;
     use ::sym::codabar::*;
+    use ::sym::rm4scc::*;
     use ::generators::ascii::*;
 
     #[test]
@@ -380,4 +389,35 @@ mod tests {
 # ##  # ## # ## #  # ## # ## # ## # # #  ## # # ##  #
 ".trim());
     }
+
+    #[test]
+    fn kix_as_ascii() {
+        let kix = KIX::new("1").unwrap();
+        let ascii = ASCII{height: 6, xdim: 1};
+        let generated = ascii.generate_layout(&kix.layout()).unwrap();
+
+        assert_eq!(generated,
+"
+    # #
+    # #
+# # # #
+# # # #
+  #   #
+  #   #
+".trim_matches('\n'));
+    }
+
+    #[test]
+    fn rm4scc_as_ascii() {
+        let rm4scc = RM4SCC::new("0").unwrap();
+        let ascii = ASCII{height: 3, xdim: 1};
+        let generated = ascii.generate_layout(&rm4scc.layout()).unwrap();
+
+        assert_eq!(generated,
+"
+#     # #     # # #
+# # # # # # # # # #
+      # #     # # #
+".trim_matches('\n'));
+    }
 }
@@ -19,12 +19,17 @@
 //! let png = Image::png(100);
 //! ```
 //!
+//! Barcodes with more than one row, such as ITF-14 with bearer bars, stacked GS1 DataBar, or
+//! height-modulated and 4-state postal barcodes, are generated from their `Layout` via
+//! `generate_layout` or `generate_layout_buffer`.
+//!
 //! See the README for more examples.
 
 extern crate image;
 
 use image::{ImageBuffer, Rgba, ImageRgba8, DynamicImage};
 use error::{Result, Error};
+use sym::layout::Layout;
  
 macro_rules! image_variants {
     ( $( #[$attr:meta] $v:ident ),* ) => {
@@ -83,7 +88,7 @@ pub struct Color {
 impl Color {
     /// Constructor.
     pub fn new(rgba: [u8; 4]) -> Color {
-        Color{rgba: rgba}
+        Color{rgba}
     }
 
     /// Constructor for black (#000000).
@@ -96,7 +101,7 @@ impl Color {
         Color::new([255, 255, 255, 255])
     }
 
-    fn to_rgba(&self) -> Rgba<u8> {
+    fn to_rgba(self) -> Rgba<u8> {
         Rgba(self.rgba)
     }
 }
@@ -149,6 +154,12 @@ impl Image {
     /// Generates the given barcode. Returns a `Result<Vec<u8>, Error>` of the encoded bytes or
     /// an error message.
     pub fn generate<T: AsRef<[u8]>>(&self, barcode: T) -> Result<Vec<u8>> {
+        self.generate_layout(&Layout::from(barcode.as_ref()))
+    }
+
+    /// Generates the given barcode layout, scaling the rows to the height of the image.
+    /// Returns a `Result<Vec<u8>, Error>` of the encoded bytes or an error message.
+    pub fn generate_layout(&self, layout: &Layout) -> Result<Vec<u8>> {
         let format = match *self {
             Image::GIF{..} => image::GIF,
             Image::PNG{..} => image::PNG,
@@ -156,7 +167,7 @@ impl Image {
             _ => return Err(Error::Generate)
         };
         let mut bytes: Vec<u8> = vec![];
-        let img = self.place_pixels(&barcode);
+        let img = self.place_pixels(layout);
 
         match img.write_to(&mut bytes, format) {
             Ok(_) => Ok(bytes),
@@ -167,27 +178,35 @@ impl Image {
     /// Generates the given barcode to an image::ImageBuffer. Returns a `Result<ImageBuffer<Rgba<u8>, Vec<u8>>, Error>`
     /// of the encoded bytes or an error message.
     pub fn generate_buffer<T: AsRef<[u8]>>(&self, barcode: T) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>> {
-        let img = self.place_pixels(&barcode);
+        self.generate_layout_buffer(&Layout::from(barcode.as_ref()))
+    }
+
+    /// Generates the given barcode layout to an image::ImageBuffer, scaling the rows to the
+    /// height of the image. Returns a `Result<ImageBuffer<Rgba<u8>, Vec<u8>>, Error>` of the
+    /// encoded bytes or an error message.
+    pub fn generate_layout_buffer(&self, layout: &Layout) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        let img = self.place_pixels(layout);
 
         Ok(img.to_rgba())
     }
 
-    fn place_pixels<T: AsRef<[u8]>>(&self, barcode: T) -> DynamicImage {
-        let barcode = barcode.as_ref();
+    fn place_pixels(&self, layout: &Layout) -> DynamicImage {
         let (xdim, height, rotation, bg, fg) = expand_image_variants!(
             *self,
             {height: h, xdim: x, rotation: r, background: b, foreground: f} => (x, h, r, b.to_rgba(), f.to_rgba()),
             GIF, PNG, JPEG, ImageBuffer
         );
-        let width = (barcode.len() as u32) * xdim;
-        let mut buffer = ImageBuffer::new(width, height);
+        let width = (layout.width() as u32) * xdim;
+        let mut buffer = ImageBuffer::from_pixel(width, height, bg);
 
-        for y in 0..height {
-            for (i, &b) in barcode.iter().enumerate() {
-                let c = if b == 0 { bg } else { fg };
+        for (row, (offset, row_height)) in layout.rows.iter().zip(layout.scale(height)) {
+            for y in offset..(offset + row_height) {
+                for (i, &b) in row.modules.iter().enumerate() {
+                    let c = if b == 0 { bg } else { fg };
 
-                for p in 0..xdim {
-                    buffer.put_pixel((i as u32 * xdim) + p, y, c);
+                    for p in 0..xdim {
+                        buffer.put_pixel((i as u32 * xdim) + p, y, c);
+                    }
                 }
             }
         }
@@ -216,6 +235,8 @@ mod tests {
     use sym::ean_supp::*;
     use sym::tf::*;
     use sym::codabar::*;
+    use sym::imb::*;
+    use sym::postnet::*;
     use generators::image::*;
     use std::io::prelude::*;
     use std::io::BufWriter;
@@ -226,13 +247,13 @@ mod tests {
     const WRITE_TO_FILE: bool = true;
 
     fn open_file(name: &'static str) -> File {
-        File::create(&Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
+        File::create(Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
     }
 
     fn write_file(bytes: &[u8], file: &'static str) {
         let path = open_file(file);
         let mut writer = BufWriter::new(path);
-        writer.write(bytes).unwrap();
+        writer.write_all(bytes).unwrap();
     }
 
     #[test]
@@ -299,7 +320,7 @@ mod tests {
 
     #[test]
     fn ean_13_as_image_buffer() {
-        let ean13 = EAN13::new("7503995991130").unwrap();
+        let ean13 = EAN13::new("7503995991139").unwrap();
         let img = Image::ImageBuffer {
             height: 99,
             xdim: 1,
@@ -310,7 +331,7 @@ mod tests {
         let generated = img.generate_buffer(&ean13.encode()[..]).unwrap();
 
         assert_eq!(generated.height(), 99);
-        assert_eq!(generated.width(), 102);
+        assert_eq!(generated.width(), 95);
     }
 
     #[test]
@@ -755,6 +776,74 @@ mod tests {
         assert_eq!(generated.len(), 3478);
     }
 
+    #[test]
+    fn itf14_as_png() {
+        let itf14 = ITF14::new("1540014128876").unwrap().with_bearer(Bearer::TopBottom);
+        let png = Image::png(84);
+        let generated = png.generate_layout(&itf14.layout()).unwrap();
+
+        if WRITE_TO_FILE { write_file(&generated[..], "itf14.png"); }
+
+        assert_eq!(generated.len(), 2182);
+    }
+
+    #[test]
+    fn itf14_as_imagebuffer() {
+        let itf14 = ITF14::new("1540014128876").unwrap();
+        let img = Image::image_buffer(84);
+        let generated = img.generate_layout_buffer(&itf14.layout()).unwrap();
+        let black = Rgba([0, 0, 0, 255]);
+        let white = Rgba([255, 255, 255, 255]);
+
+        assert_eq!(generated.height(), 84);
+        assert_eq!(generated.width(), 165);
+        assert_eq!(*generated.get_pixel(80, 0), black);
+        assert_eq!(*generated.get_pixel(0, 40), black);
+        assert_eq!(*generated.get_pixel(10, 40), white);
+        assert_eq!(*generated.get_pixel(15, 40), black);
+        assert_eq!(*generated.get_pixel(80, 83), black);
+    }
+
+    #[test]
+    fn imb_as_imagebuffer() {
+        let imb = IMB::new("01234567094987654321").unwrap();
+        let img = Image::image_buffer(30);
+        let generated = img.generate_layout_buffer(&imb.layout()).unwrap();
+        let black = Rgba([0, 0, 0, 255]);
+        let white = Rgba([255, 255, 255, 255]);
+
+        assert_eq!(generated.height(), 30);
+        assert_eq!(generated.width(), 129);
+        // Ascender.
+        assert_eq!(*generated.get_pixel(0, 5), black);
+        assert_eq!(*generated.get_pixel(0, 25), white);
+        // Tracker.
+        assert_eq!(*generated.get_pixel(2, 5), white);
+        assert_eq!(*generated.get_pixel(2, 15), black);
+        assert_eq!(*generated.get_pixel(2, 25), white);
+        // Full.
+        assert_eq!(*generated.get_pixel(6, 5), black);
+        assert_eq!(*generated.get_pixel(6, 25), black);
+    }
+
+    #[test]
+    fn planet_as_imagebuffer() {
+        let planet = PLANET::new("40123452356").unwrap();
+        let img = Image::image_buffer(50);
+        let generated = img.generate_layout_buffer(&planet.layout()).unwrap();
+        let black = Rgba([0, 0, 0, 255]);
+        let white = Rgba([255, 255, 255, 255]);
+
+        assert_eq!(generated.height(), 50);
+        assert_eq!(generated.width(), 123);
+        // Tall frame bar.
+        assert_eq!(*generated.get_pixel(0, 10), black);
+        assert_eq!(*generated.get_pixel(0, 40), black);
+        // Short bar.
+        assert_eq!(*generated.get_pixel(4, 10), white);
+        assert_eq!(*generated.get_pixel(4, 40), black);
+    }
+
     #[test]
     fn stf_as_png() {
         let stf = TF::standard("1234567").unwrap();
@@ -24,6 +24,7 @@ pub struct JSON {
 
 impl JSON {
     /// Returns a new JSON with default values.
+    #[allow(clippy::new_without_default)]
     pub fn new() -> JSON {
         JSON {
             height: 10,
@@ -17,8 +17,13 @@
 //! // Or use the constructor for defaults (you must specify the height).
 //! let svg = SVG::new(100);
 //! ```
+//!
+//! Barcodes with more than one row, such as ITF-14 with bearer bars, stacked GS1 DataBar, or
+//! height-modulated and 4-state postal barcodes, are generated from their `Layout` via
+//! `generate_layout`.
 
 use error::Result;
+use sym::layout::Layout;
 
 trait ToHex {
     fn to_hex(&self) -> String;
@@ -48,7 +53,7 @@ pub struct Color {
 impl Color {
     /// Constructor.
     pub fn new(rgba: [u8; 4]) -> Color {
-        Color{rgba: rgba}
+        Color{rgba}
     }
 
     /// Constructor for black (#000000).
@@ -61,7 +66,7 @@ impl Color {
         Color::new([255, 255, 255, 255])
     }
 
-    fn to_opacity(&self) -> String {
+    fn to_opacity(self) -> String {
         format!("{:.*}", 2, (self.rgba[3] as f64 / 255.0))
     }
 }
@@ -94,14 +99,14 @@ impl SVG {
     /// Returns a new SVG with default values.
     pub fn new(height: u32) -> SVG {
         SVG {
-            height: height,
+            height,
             xdim: 1,
             foreground: Color{rgba: [0, 0, 0, 255]},
             background: Color{rgba: [255, 255, 255, 255]},
         }
     }
 
-    fn rect(&self, style: u8, offset: u32, width: u32) -> String {
+    fn rect(&self, style: u8, x: u32, y: u32, width: u32, height: u32) -> String {
         let fill = match style {
             1 => self.foreground,
             _ => self.background,
@@ -112,23 +117,34 @@ impl SVG {
             o => format!(" fill-opacity=\"{}\" ", o),
         };
 
-        format!("<rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"#{}\"{}/>",
-                offset, width, self.height, fill.to_hex(), opacity)
+        format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#{}\"{}/>",
+                x, y, width, height, fill.to_hex(), opacity)
     }
 
     /// Generates the given barcode. Returns a `Result<String, Error>` of the SVG data or an
     /// error message.
     pub fn generate<T: AsRef<[u8]>>(&self, barcode: T) -> Result<String> {
-        let barcode = barcode.as_ref();
-        let width = (barcode.len() as u32) * self.xdim;
-        let rects: String = barcode.iter()
-            .enumerate()
-            .filter(|&(_, &n)| n == 1)
-            .map(|(i, &n)| self.rect(n, i as u32 * self.xdim, self.xdim))
+        self.generate_layout(&Layout::from(barcode.as_ref()))
+    }
+
+    /// Generates the given barcode layout, scaling the rows to the height of the SVG.
+    /// Returns a `Result<String, Error>` of the SVG data or an error message.
+    pub fn generate_layout(&self, layout: &Layout) -> Result<String> {
+        let width = (layout.width() as u32) * self.xdim;
+        let rects: String = layout.rows
+            .iter()
+            .zip(layout.scale(self.height))
+            .flat_map(|(row, (y, height))| {
+                row.modules
+                   .iter()
+                   .enumerate()
+                   .filter(|&(_, &n)| n == 1)
+                   .map(move |(i, &n)| self.rect(n, i as u32 * self.xdim, y, self.xdim, height))
+            })
             .collect();
 
         Ok(format!("<svg version=\"1.1\" viewBox=\"0 0 {w} {h}\">{s}{r}</svg>",
-                   w=width, h=self.height, s=self.rect(0, 0, width), r=rects))
+                   w=width, h=self.height, s=self.rect(0, 0, 0, width, self.height), r=rects))
     }
 }
 
@@ -143,6 +159,10 @@ mod tests {
     use ::sym::ean_supp::*;
     use ::sym::tf::*;
     use ::sym::codabar::*;
+    use ::sym::pharmacode::*;
+    use ::sym::imb::*;
+    use ::sym::postnet::*;
+    use ::sym::databar::*;
     use ::generators::svg::*;
     use std::io::prelude::*;
     use std::io::BufWriter;
@@ -155,11 +175,11 @@ mod tests {
     fn write_file(data: &str, file: &'static str) {
         let path = open_file(file);
         let mut writer = BufWriter::new(path);
-        writer.write(data.as_bytes()).unwrap();
+        writer.write_all(data.as_bytes()).unwrap();
     }
 
     fn open_file(name: &'static str) -> File {
-        File::create(&Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
+        File::create(Path::new(&format!("{}/{}", TEST_DATA_BASE, name)[..])).unwrap()
     }
 
     #[test]
@@ -282,6 +302,20 @@ mod tests {
         assert_eq!(generated.len(), 7123);
     }
 
+    #[test]
+    fn itf14_as_svg() {
+        let itf14 = ITF14::new("1540014128876").unwrap();
+        let svg = SVG::new(84);
+        let generated = svg.generate_layout(&itf14.layout()).unwrap();
+
+        if WRITE_TO_FILE { write_file(&generated[..], "itf14.svg"); }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 165 84\">"));
+        assert!(generated.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"10\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"15\" y=\"10\" width=\"1\" height=\"64\" fill=\"#000000\"/>"));
+        assert_eq!(generated.len(), 23797);
+    }
+
     #[test]
     fn code11_as_svg() {
         let code11 = Code11::new("9988-45643201").unwrap();
@@ -296,4 +330,63 @@ mod tests {
         assert_eq!(generated.len(), 4219);
     }
 
+    #[test]
+    fn imb_as_svg() {
+        let imb = IMB::new("01234567094987654321").unwrap();
+        let svg = SVG::new(30);
+        let generated = svg.generate_layout(&imb.layout()).unwrap();
+
+        if WRITE_TO_FILE { write_file(&generated[..], "imb.svg"); }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 129 30\">"));
+        assert!(generated.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"10\" fill=\"#000000\"/>"));
+        assert!(!generated.contains("<rect x=\"0\" y=\"20\" width=\"1\" height=\"10\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"6\" y=\"20\" width=\"1\" height=\"10\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn postnet_as_svg() {
+        let postnet = POSTNET::new("12345").unwrap();
+        let svg = SVG::new(50);
+        let generated = svg.generate_layout(&postnet.layout()).unwrap();
+
+        if WRITE_TO_FILE { write_file(&generated[..], "postnet.svg"); }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 63 50\">"));
+        assert!(generated.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"30\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"0\" y=\"30\" width=\"1\" height=\"20\" fill=\"#000000\"/>"));
+        assert!(!generated.contains("<rect x=\"2\" y=\"0\" width=\"1\" height=\"30\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"2\" y=\"30\" width=\"1\" height=\"20\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn databar_stacked_as_svg() {
+        let databar = DataBar::stacked("0001234567890").unwrap();
+        let layout = databar.layout();
+        let svg = SVG::new(layout.height() * 2);
+        let generated = svg.generate_layout(&layout).unwrap();
+
+        if WRITE_TO_FILE { write_file(&generated[..], "databar_stacked.svg"); }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 50 26\">"));
+        // Top row, separator and bottom row.
+        assert!(generated.contains("<rect x=\"1\" y=\"0\" width=\"1\" height=\"10\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"4\" y=\"10\" width=\"1\" height=\"2\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"0\" y=\"12\" width=\"1\" height=\"14\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn pharmacode_two_track_as_svg() {
+        let pharmacode = PharmacodeTwoTrack::new("1234").unwrap();
+        let svg = SVG::new(40);
+        let generated = svg.generate_layout(&pharmacode.layout()).unwrap();
+
+        if WRITE_TO_FILE { write_file(&generated[..], "pharmacode_two_track.svg"); }
+
+        assert!(generated.starts_with("<svg version=\"1.1\" viewBox=\"0 0 13 40\">"));
+        assert!(generated.contains("<rect x=\"0\" y=\"20\" width=\"1\" height=\"20\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"6\" y=\"0\" width=\"1\" height=\"20\" fill=\"#000000\"/>"));
+        assert!(generated.contains("<rect x=\"6\" y=\"20\" width=\"1\" height=\"20\" fill=\"#000000\"/>"));
+    }
+
 }
@@ -14,15 +14,29 @@
 //!   * JAN
 //!   * Bookland
 //! * EAN-8
+//! * UPC-E
 //! * EAN Supplementals
 //!   * EAN-2
 //!   * EAN-5
 //! * Code39
 //! * Code128
+//!   * GS1-128
 //! * Two-Of-Five
 //!   * Interleaved (ITF)
+//!   * ITF-14
 //!   * Standard (STF)
+//!   * Industrial, Matrix, IATA, Datalogic, COOP
 //! * Codabar
+//! * MSI Plessey
+//! * Pharmacode (one-track, two-track)
+//! * USPS Intelligent Mail (IMb)
+//! * Royal Mail 4-State Customer Code (RM4SCC)
+//! * KIX
+//! * USPS POSTNET and PLANET
+//! * Australia Post 4-State Customer Barcodes
+//! * Japan Post Customer Barcode
+//! * GS1 DataBar Omnidirectional, Truncated, Stacked and Stacked Omnidirectional
+//! * GS1 DataBar Limited
 //! * More coming!
 //!
 //! ### Generators
@@ -0,0 +1,353 @@
+//! Encoder for Australia Post 4-state customer barcodes.
+//!
+//! Australia Post barcodes encode a format control code (FCC) and an 8 digit delivery point
+//! identifier (DPID), optionally followed by customer information, between a pair of start and
+//! stop bars. Four Reed-Solomon parity symbols are appended before the stop bars.
+//!
+//! There are three formats, chosen by the length of the customer information:
+//!
+//! * Standard Customer Barcode (37 bars), without customer information.
+//! * Customer Barcode 2 (52 bars), with up to 8 digits or 5 characters.
+//! * Customer Barcode 3 (67 bars), with up to 15 digits or 10 characters.
+//!
+//! Customer information is encoded with either the N table (two bars per digit) or the C table
+//! (three bars per character). Any unused bars are filled with tracker bars.
+
+use sym::Parse;
+use sym::layout::{Layout, State};
+use error::{Error, Result};
+use std::ops::Range;
+
+// The bars of the N table, where 0 = full, 1 = ascender, 2 = descender and 3 = tracker.
+const N_TABLE: [[u8; 2]; 10] = [
+    [0, 0], [0, 1], [0, 2], [1, 0], [1, 1], [1, 2], [2, 0], [2, 1], [2, 2], [3, 0],
+];
+
+// The characters of the C table, in order of their encodings.
+const C_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz #";
+
+// The bars of the C table, as above.
+const C_TABLE: [[u8; 3]; 64] = [
+    [2, 2, 2], [3, 0, 0], [3, 0, 1], [3, 0, 2], [3, 1, 0], [3, 1, 1], [3, 1, 2], [3, 2, 0],
+    [3, 2, 1], [3, 2, 2], [0, 0, 0], [0, 0, 1], [0, 0, 2], [0, 1, 0], [0, 1, 1], [0, 1, 2],
+    [0, 2, 0], [0, 2, 1], [0, 2, 2], [1, 0, 0], [1, 0, 1], [1, 0, 2], [1, 1, 0], [1, 1, 1],
+    [1, 1, 2], [1, 2, 0], [1, 2, 1], [1, 2, 2], [2, 0, 0], [2, 0, 1], [2, 0, 2], [2, 1, 0],
+    [2, 1, 1], [2, 1, 2], [2, 2, 0], [2, 2, 1], [0, 2, 3], [0, 3, 0], [0, 3, 1], [0, 3, 2],
+    [0, 3, 3], [1, 0, 3], [1, 1, 3], [1, 2, 3], [1, 3, 0], [1, 3, 1], [1, 3, 2], [1, 3, 3],
+    [2, 0, 3], [2, 1, 3], [2, 2, 3], [2, 3, 0], [2, 3, 1], [2, 3, 2], [2, 3, 3], [3, 0, 3],
+    [3, 1, 3], [3, 2, 3], [3, 3, 0], [3, 3, 1], [3, 3, 2], [3, 3, 3], [0, 0, 3], [0, 1, 3],
+];
+
+// The start and stop bars.
+const START_STOP: [u8; 2] = [1, 3];
+
+// The primitive polynomial of GF(64), x^6 + x + 1, used for the Reed-Solomon parity.
+const GF_POLYNOMIAL: u8 = 0x43;
+
+// The Reed-Solomon generator polynomial (x - a)(x - a^2)(x - a^3)(x - a^4), highest power first.
+const RS_GENERATOR: [u8; 5] = [1, 30, 29, 17, 48];
+
+/// The formats of Australia Post customer barcodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AusPostFormat {
+    /// The 37-bar Standard Customer Barcode (FCC 11).
+    Standard,
+    /// The 52-bar Customer Barcode 2 (FCC 59).
+    CustomerBarcode2,
+    /// The 67-bar Customer Barcode 3 (FCC 62).
+    CustomerBarcode3,
+}
+
+impl AusPostFormat {
+    /// Returns the format control code of the format.
+    pub fn fcc(self) -> u8 {
+        match self {
+            AusPostFormat::Standard => 11,
+            AusPostFormat::CustomerBarcode2 => 59,
+            AusPostFormat::CustomerBarcode3 => 62,
+        }
+    }
+
+    /// Returns the number of bars in barcodes of the format.
+    pub fn bars(self) -> usize {
+        match self {
+            AusPostFormat::Standard => 37,
+            AusPostFormat::CustomerBarcode2 => 52,
+            AusPostFormat::CustomerBarcode3 => 67,
+        }
+    }
+}
+
+/// The encoding tables of the customer information field.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CustomerEncoding {
+    /// The N table, encoding digits as two bars each.
+    N,
+    /// The C table, encoding digits, letters, space and '#' as three bars each.
+    C,
+}
+
+/// The Australia Post customer barcode type.
+#[derive(Debug)]
+pub struct AusPost {
+    dpid: Vec<u8>,
+    info: Vec<char>,
+    encoding: CustomerEncoding,
+    format: AusPostFormat,
+}
+
+impl AusPost {
+    /// Creates a new barcode.
+    /// The data is the 8 digit DPID, optionally followed by the customer information, which is
+    /// encoded with the N table if it is numeric and the C table otherwise.
+    /// Returns Result<AusPost, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<AusPost> {
+        let numeric = data.as_ref().chars().skip(8).all(|c| c.is_ascii_digit());
+        let encoding = if numeric { CustomerEncoding::N } else { CustomerEncoding::C };
+
+        AusPost::with_encoding(data, encoding)
+    }
+
+    /// Creates a new barcode, encoding the customer information with the given table.
+    /// Returns Result<AusPost, Error> indicating parse success.
+    pub fn with_encoding<T: AsRef<str>>(data: T, encoding: CustomerEncoding) -> Result<AusPost> {
+        let d = AusPost::parse(data.as_ref())?;
+        let (dpid, info) = d.split_at(8);
+
+        if !dpid.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::Character);
+        }
+
+        let (cb2, cb3) = match encoding {
+            CustomerEncoding::N if !info.chars().all(|c| c.is_ascii_digit()) => {
+                return Err(Error::Character)
+            }
+            CustomerEncoding::N => (8, 15),
+            CustomerEncoding::C => (5, 10),
+        };
+        let format = match info.len() {
+            0 => AusPostFormat::Standard,
+            n if n <= cb2 => AusPostFormat::CustomerBarcode2,
+            n if n <= cb3 => AusPostFormat::CustomerBarcode3,
+            _ => return Err(Error::Length),
+        };
+
+        Ok(AusPost {
+            dpid: dpid.bytes().map(|b| b - b'0').collect(),
+            info: info.chars().collect(),
+            encoding,
+            format,
+        })
+    }
+
+    /// Returns the format of the barcode.
+    pub fn format(&self) -> AusPostFormat {
+        self.format
+    }
+
+    /// Returns the encoding table of the customer information.
+    pub fn encoding(&self) -> CustomerEncoding {
+        self.encoding
+    }
+
+    /// Returns the 8 digit delivery point identifier.
+    pub fn dpid(&self) -> String {
+        self.dpid.iter().map(|d| d.to_string()).collect()
+    }
+
+    /// Returns the customer information, which may be empty.
+    pub fn customer_info(&self) -> String {
+        self.info.iter().collect()
+    }
+
+    /// Encodes the FCC, DPID and customer information, filling the unused bars with trackers.
+    fn data_bars(&self) -> Vec<u8> {
+        let fcc = self.format.fcc();
+        let mut bars = vec![];
+
+        for &d in [fcc / 10, fcc % 10].iter().chain(self.dpid.iter()) {
+            bars.extend(&N_TABLE[d as usize]);
+        }
+
+        for &c in &self.info {
+            match self.encoding {
+                CustomerEncoding::N => {
+                    let d = c.to_digit(10).expect("Unknown character");
+                    bars.extend(&N_TABLE[d as usize]);
+                }
+                CustomerEncoding::C => {
+                    let i = C_CHARS.chars().position(|t| t == c).expect("Unknown character");
+                    bars.extend(&C_TABLE[i]);
+                }
+            }
+        }
+
+        let len = self.format.bars() - START_STOP.len() * 2 - 12;
+        bars.resize(len, 3);
+        bars
+    }
+
+    /// Multiplies two elements of GF(64).
+    fn gf_multiply(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0;
+
+        while b > 0 {
+            if b & 1 != 0 {
+                product ^= a;
+            }
+
+            a <<= 1;
+            if a & 0x40 != 0 {
+                a ^= GF_POLYNOMIAL;
+            }
+            b >>= 1;
+        }
+
+        product
+    }
+
+    /// Calculates the 4 Reed-Solomon parity symbols of the given 6-bit data symbols.
+    fn parity(symbols: &[u8]) -> [u8; 4] {
+        let mut parity = [0; 4];
+
+        for &s in symbols {
+            let m = s ^ parity[0];
+
+            for i in 0..4 {
+                let next = if i < 3 { parity[i + 1] } else { 0 };
+                parity[i] = next ^ AusPost::gf_multiply(m, RS_GENERATOR[i + 1]);
+            }
+        }
+
+        parity
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<State> of the bars, from left to right, including the start and stop bars.
+    pub fn encode(&self) -> Vec<State> {
+        let data = self.data_bars();
+        let symbols: Vec<u8> = data.chunks(3).map(|c| c[0] << 4 | c[1] << 2 | c[2]).collect();
+        let mut bars = START_STOP.to_vec();
+
+        bars.extend(data);
+
+        for &p in AusPost::parity(&symbols[..]).iter() {
+            bars.extend(&[p >> 4, p >> 2 & 3, p & 3]);
+        }
+
+        bars.extend(&START_STOP);
+        bars.iter()
+            .map(|&b| match b {
+                0 => State::Full,
+                1 => State::Ascender,
+                2 => State::Descender,
+                _ => State::Tracker,
+            })
+            .collect()
+    }
+
+    /// Returns the layout of the barcode, with rows for the ascenders, the tracker and the
+    /// descenders.
+    pub fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+impl Parse for AusPost {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        8..23
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        C_CHARS.chars().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::auspost::*;
+    use sym::layout::State;
+    use error::Error;
+
+    fn collapse_states(v: Vec<State>) -> String {
+        v.iter().map(|s| s.to_char()).collect()
+    }
+
+    #[test]
+    fn new_auspost() {
+        let auspost1 = AusPost::new("39987520").unwrap();
+        let auspost2 = AusPost::new("3998752012345678").unwrap();
+        let auspost3 = AusPost::new("39987520ABC").unwrap();
+        let auspost4 = AusPost::new("39987520123456789").unwrap();
+
+        assert_eq!(auspost1.format(), AusPostFormat::Standard);
+        assert_eq!(auspost2.format(), AusPostFormat::CustomerBarcode2);
+        assert_eq!(auspost2.encoding(), CustomerEncoding::N);
+        assert_eq!(auspost3.format(), AusPostFormat::CustomerBarcode2);
+        assert_eq!(auspost3.encoding(), CustomerEncoding::C);
+        assert_eq!(auspost4.format(), AusPostFormat::CustomerBarcode3);
+        assert_eq!(auspost4.dpid(), "39987520");
+        assert_eq!(auspost4.customer_info(), "123456789");
+    }
+
+    #[test]
+    fn invalid_data_auspost() {
+        assert_eq!(AusPost::new("3998752A").err().unwrap(), Error::Character);
+        assert_eq!(AusPost::new("39987520AB-C").err().unwrap(), Error::Character);
+        assert_eq!(AusPost::with_encoding("39987520ABC", CustomerEncoding::N).err().unwrap(),
+                   Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_auspost() {
+        assert_eq!(AusPost::new("3998752").err().unwrap(), Error::Length);
+        assert_eq!(AusPost::new("399875201234567890123456").err().unwrap(), Error::Length);
+        assert_eq!(AusPost::new("39987520ABCDEFGHIJK").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn auspost_encode() {
+        let auspost1 = AusPost::new("39987520").unwrap();
+        let auspost2 = AusPost::new("56439111").unwrap();
+
+        assert_eq!(collapse_states(auspost1.encode()), "ATFAFAAFTFTFDDDAADFDFFTTFDADATAFTFDAT");
+        assert_eq!(collapse_states(auspost2.encode()), "ATFAFAADDFAAAFTFFAFAFATTATTFDATFTTDAT");
+    }
+
+    #[test]
+    fn auspost_customer_barcode_2_encode() {
+        let auspost1 = AusPost::new("3998752012345678").unwrap();
+        let auspost2 = AusPost::new("39987520ABC").unwrap();
+
+        assert_eq!(collapse_states(auspost1.encode()), "ATADTFAFTFTFDDDAADFDFFFAFDAFAAADDFDADDDFDAAFDFDFTDAT");
+        assert_eq!(collapse_states(auspost2.encode()), "ATADTFAFTFTFDDDAADFDFFFFFFFAFFDTTTTTTTATFTADTDTTAAAT");
+    }
+
+    #[test]
+    fn auspost_customer_barcode_3_encode() {
+        let auspost1 = AusPost::new("39987520123456789012345").unwrap();
+        let auspost2 = AusPost::new("39987520Ab 1#z").unwrap();
+
+        assert_eq!(collapse_states(auspost1.encode()), "ATDFFDAFTFTFDDDAADFDFFFAFDAFAAADDFDADDTFFFFAFDAFAAADTADTAATAFTDFTAT");
+        assert_eq!(collapse_states(auspost2.encode()), "ATDFFDAFTFTFDDDAADFDFFFFFFTFFFTTFFFATTTTTTTTTTTTTTTTTDDFATTDATDAFAT");
+    }
+
+    #[test]
+    fn auspost_parity() {
+        // A codeword with its parity symbols is divisible by the generator polynomial.
+        let mut symbols = vec![5, 17, 42, 63, 0, 9];
+        symbols.extend(&AusPost::parity(&symbols[..]));
+
+        assert_eq!(AusPost::parity(&symbols[..]), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn auspost_layout() {
+        let layout = AusPost::new("39987520").unwrap().layout();
+
+        assert_eq!(layout.rows.len(), 3);
+        assert_eq!(layout.width(), 37 * 2 - 1);
+    }
+}
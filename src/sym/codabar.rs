@@ -6,9 +6,13 @@
 //!
 //! Barcodes of this variant should start and end with either A, B, C, or D depending on
 //! the industry.
+//!
+//! Barcodes can also be created from unframed data via `Codabar::with_options`, which adds the
+//! chosen start and stop characters (also accepting the alternate T, N, * and E notation) and an
+//! optional modulo-16 check character.
 
-use sym::Parse;
-use error::Result;
+use sym::{Barcode, Parse, Ratio, helpers};
+use error::{Error, Result};
 use std::ops::Range;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -48,6 +52,21 @@ impl Unit {
         }
     }
 
+    // The start and stop characters, with their alternate notation.
+    fn from_guard_char(c: char) -> Option<Unit> {
+        match c {
+            'A' | 'T' => Some(Unit::A),
+            'B' | 'N' => Some(Unit::B),
+            'C' | '*' => Some(Unit::C),
+            'D' | 'E' => Some(Unit::D),
+            _ => None
+        }
+    }
+
+    fn is_guard(self) -> bool {
+        matches!(self, Unit::A | Unit::B | Unit::C | Unit::D)
+    }
+
     fn from_char(c: char) -> Option<Unit> {
         match c {
             '0' => Some(Unit::Zero),
@@ -73,11 +92,76 @@ impl Unit {
             _ => None
         }
     }
+
+    fn to_char(self) -> char {
+        match self {
+            Unit::Zero => '0',
+            Unit::One => '1',
+            Unit::Two => '2',
+            Unit::Three => '3',
+            Unit::Four => '4',
+            Unit::Five => '5',
+            Unit::Six => '6',
+            Unit::Seven => '7',
+            Unit::Eight => '8',
+            Unit::Nine => '9',
+            Unit::Dash => '-',
+            Unit::Dollar => '$',
+            Unit::Slash => '/',
+            Unit::Colon => ':',
+            Unit::Point => '.',
+            Unit::Plus => '+',
+            Unit::A => 'A',
+            Unit::B => 'B',
+            Unit::C => 'C',
+            Unit::D => 'D',
+        }
+    }
+}
+
+// Units in order of their values for the modulo-16 checksum.
+const UNITS: [Unit; 16] = [
+    Unit::Zero, Unit::One, Unit::Two, Unit::Three, Unit::Four, Unit::Five, Unit::Six, Unit::Seven,
+    Unit::Eight, Unit::Nine, Unit::Dash, Unit::Dollar, Unit::Colon, Unit::Slash, Unit::Point,
+    Unit::Plus,
+];
+
+/// The check characters appended to a Codabar barcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CodabarCheck {
+    /// No check character.
+    None,
+    /// A modulo-16 check character, before the stop character.
+    Mod16,
+}
+
+/// The options for creating a Codabar barcode from unframed data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CodabarOptions {
+    /// The start character. One of A, B, C or D, or T, N, * or E in the alternate notation.
+    pub start: char,
+    /// The stop character. One of A, B, C or D, or T, N, * or E in the alternate notation.
+    pub stop: char,
+    /// The check character to append.
+    pub check: CodabarCheck,
+}
+
+impl Default for CodabarOptions {
+    fn default() -> CodabarOptions {
+        CodabarOptions {
+            start: 'A',
+            stop: 'A',
+            check: CodabarCheck::None,
+        }
+    }
 }
 
 /// The Codabar barcode type.
 #[derive(Debug)]
-pub struct Codabar(Vec<Unit>);
+pub struct Codabar {
+    units: Vec<Unit>,
+    check: Option<Unit>,
+}
 
 impl Codabar {
     /// Creates a new barcode.
@@ -88,26 +172,103 @@ impl Codabar {
                      .map(|c| Unit::from_char(c).unwrap())
                      .collect();
 
-        Ok(Codabar(units))
+        Ok(Codabar { units, check: None })
+    }
+
+    /// Creates a new barcode from data without start and stop characters, which are added
+    /// according to the given options.
+    /// Returns Result<Codabar, Error> indicating parse success.
+    pub fn with_options<T: AsRef<str>>(data: T, options: CodabarOptions) -> Result<Codabar> {
+        let data = data.as_ref();
+        let valid_len = Codabar::valid_len();
+        let data_len = data.chars().count() as u32;
+
+        // The start and stop characters count towards the length of the barcode.
+        if data_len < valid_len.start || data_len > valid_len.end - 2 {
+            return Err(Error::Length);
+        }
+
+        let guard = |c: char| Unit::from_guard_char(c.to_ascii_uppercase()).ok_or(Error::Character);
+        let mut units = vec![guard(options.start)?];
+
+        for c in data.chars() {
+            match Unit::from_char(c) {
+                Some(u) if !u.is_guard() => units.push(u),
+                _ => return Err(Error::Character),
+            }
+        }
+
+        units.push(guard(options.stop)?);
+
+        let mut codabar = Codabar { units, check: None };
+
+        if options.check == CodabarCheck::Mod16 {
+            codabar.check = Some(codabar.checksum_unit());
+        }
+
+        Ok(codabar)
+    }
+
+    /// Returns the data without the start and stop characters, or the check character.
+    pub fn unframed_data(&self) -> String {
+        let mut units = &self.units[..];
+
+        if units.first().map_or(false, |u| u.is_guard()) {
+            units = &units[1..];
+        }
+
+        if units.last().map_or(false, |u| u.is_guard()) {
+            units = &units[..units.len() - 1];
+        }
+
+        units.iter().map(|u| u.to_char()).collect()
+    }
+
+    /// Calculates the check character using a modulo-16 algorithm over all characters,
+    /// including the start and stop characters.
+    fn checksum_unit(&self) -> Unit {
+        let sum: usize = self.units.iter().map(|&u| u as usize).sum();
+
+        UNITS[(16 - sum % 16) % 16]
+    }
+
+    /// Returns the characters in the order they are encoded, with the check character (if any)
+    /// placed before the stop character.
+    fn encoded_units(&self) -> Vec<Unit> {
+        let mut units = self.units.clone();
+
+        if let Some(check) = self.check {
+            let len = units.len();
+            units.insert(len - 1, check);
+        }
+
+        units
     }
 
     /// Encodes the barcode.
     /// Returns a Vec<u8> of binary digits.
     pub fn encode(&self) -> Vec<u8> {
         let mut enc: Vec<u8> = vec![];
+        let units = self.encoded_units();
 
-        for (i, u) in self.0.iter().enumerate() {
+        for (i, u) in units.iter().enumerate() {
             enc.extend(u.lookup()
                         .iter()
                         .cloned());
 
-            if i < self.0.len() - 1 {
+            if i < units.len() - 1 {
                 enc.push(0);
             }
         }
 
         enc
     }
+
+    /// Encodes the barcode using the given wide-to-narrow ratio (the default is 2:1).
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode_with_ratio(&self, ratio: Ratio) -> Vec<u8> {
+        helpers::apply_ratio(&self.encode()[..], ratio)
+    }
 }
 
 impl Parse for Codabar {
@@ -124,9 +285,33 @@ impl Parse for Codabar {
     }
 }
 
+impl Barcode for Codabar {
+    fn encode(&self) -> Vec<u8> {
+        Codabar::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "Codabar"
+    }
+
+    fn data(&self) -> String {
+        self.units.iter().map(|u| u.to_char()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        self.check.map(|u| u.to_char().to_string())
+    }
+
+    // The check character is printed before the stop character.
+    fn text(&self) -> String {
+        self.encoded_units().iter().map(|u| u.to_char()).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::codabar::*;
+    use sym::{Barcode, Ratio};
     use error::Error;
     use std::char;
 
@@ -157,4 +342,59 @@ mod tests {
         assert_eq!(collapse_vec(codabar_a.encode()), "1011001001010101100101010010110110010101010110100101010010011");
         assert_eq!(collapse_vec(codabar_b.encode()), "10110010010101101001010101001101010110010110101001010010101101010010011");
     }
+
+    #[test]
+    fn codabar_as_barcode() {
+        let codabar = Codabar::new("A40156B").unwrap();
+
+        assert_eq!(codabar.symbology(), "Codabar");
+        assert_eq!(codabar.data(), "A40156B");
+        assert_eq!(codabar.checksum(), None);
+    }
+
+    #[test]
+    fn codabar_encode_with_ratio() {
+        let codabar = Codabar::new("A1B").unwrap();
+
+        assert_eq!(codabar.encode_with_ratio(Ratio::new(4).unwrap()), codabar.encode());
+        assert_eq!(collapse_vec(codabar.encode_with_ratio(Ratio::new(6).unwrap())), "101110001000101010111000101010001000111");
+    }
+
+    #[test]
+    fn codabar_with_options() {
+        let options = CodabarOptions { start: 'A', stop: 'B', check: CodabarCheck::None };
+        let codabar = Codabar::with_options("1234", options).unwrap();
+        let alternate = Codabar::with_options("1234", CodabarOptions { start: 't', stop: 'N', ..options }).unwrap();
+
+        assert_eq!(codabar.encode(), Codabar::new("A1234B").unwrap().encode());
+        assert_eq!(alternate.encode(), codabar.encode());
+        assert_eq!(codabar.data(), "A1234B");
+        assert_eq!(codabar.unframed_data(), "1234");
+        assert_eq!(Codabar::with_options("", options).err().unwrap(), Error::Length);
+        assert_eq!(Codabar::with_options("1".repeat(255), options).err().unwrap(), Error::Length);
+        assert!(Codabar::with_options("1".repeat(254), options).is_ok());
+        assert_eq!(Codabar::with_options("12B4", options).err().unwrap(), Error::Character);
+        assert_eq!(Codabar::with_options("1234", CodabarOptions { stop: 'F', ..options }).err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn codabar_with_checksum() {
+        let options = CodabarOptions { start: 'A', stop: 'B', check: CodabarCheck::Mod16 };
+        let codabar1 = Codabar::with_options("1234", options).unwrap(); // Check character: 5
+        let codabar2 = Codabar::with_options("12345", options).unwrap(); // Check character: 0
+        let codabar3 = Codabar::with_options("37859", CodabarOptions { start: 'E', stop: '*', ..options }).unwrap();
+
+        assert_eq!(codabar1.checksum(), Some("5".to_owned()));
+        assert_eq!(codabar1.text(), "A12345B");
+        assert_eq!(codabar1.encode(), Codabar::new("A12345B").unwrap().encode());
+        assert_eq!(codabar2.checksum(), Some("0".to_owned()));
+        assert_eq!(codabar2.unframed_data(), "12345");
+        assert_eq!(codabar3.text(), "D37859$C");
+    }
+
+    #[test]
+    fn codabar_unframed_data() {
+        assert_eq!(Codabar::new("A40156B").unwrap().unframed_data(), "40156");
+        assert_eq!(Codabar::new("40156").unwrap().unframed_data(), "40156");
+    }
 }
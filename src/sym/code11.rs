@@ -3,10 +3,11 @@
 //! Code11 is able to encode all of the decimal digits and the dash character. It is mainly
 //! used in the telecommunications industry.
 //!
-//! Code11 is a discrete symbology. This encoder always provides a C checksum. For barcodes longer
-//! than 10 characters, a second checksum digit (K) is appended.
+//! Code11 is a discrete symbology. By default, this encoder always provides a C checksum. For
+//! barcodes longer than 10 characters, a second checksum digit (K) is appended. The check
+//! characters can be chosen explicitly via `Code11::with_options`.
 
-use sym::{Parse, helpers};
+use sym::{Barcode, Parse, Ratio, helpers};
 use error::Result;
 use std::ops::Range;
 
@@ -23,9 +24,41 @@ const CHARS: [(char, &[u8]); 11] = [
 const GUARD: [u8; 7] = [1,0,1,1,0,0,1];
 const SEPARATOR: [u8; 1] = [0];
 
+/// The check characters appended to a Code11 barcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Code11Check {
+    /// A C check character, followed by a K check character for barcodes longer than 10
+    /// characters.
+    Auto,
+    /// No check characters.
+    None,
+    /// A C check character.
+    C,
+    /// A C check character, followed by a K check character.
+    CK,
+}
+
+/// The options for creating a Code11 barcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Code11Options {
+    /// The check characters to append.
+    pub check: Code11Check,
+}
+
+impl Default for Code11Options {
+    fn default() -> Code11Options {
+        Code11Options {
+            check: Code11Check::Auto,
+        }
+    }
+}
+
 /// The Code11 barcode type.
 #[derive(Debug)]
-pub struct Code11(Vec<char>);
+pub struct Code11 {
+    chars: Vec<char>,
+    check: Code11Check,
+}
 
 /// The USD-8 barcode type.
 pub type USD8 = Code11;
@@ -34,16 +67,47 @@ impl Code11 {
     /// Creates a new barcode.
     /// Returns Result<Code11, Error> indicating parse success.
     pub fn new<T: AsRef<str>>(data: T) -> Result<Code11> {
-        Code11::parse(data.as_ref()).and_then(|d| {
-            Ok(Code11(d.chars()
-                       .collect()))
+        Code11::with_options(data, Code11Options::default())
+    }
+
+    /// Creates a new barcode with the given options.
+    /// Returns Result<Code11, Error> indicating parse success.
+    pub fn with_options<T: AsRef<str>>(data: T, options: Code11Options) -> Result<Code11> {
+        Code11::parse(data.as_ref()).map(|d| {
+            Code11 {
+                chars: d.chars().collect(),
+                check: options.check,
+            }
         })
     }
 
+    /// Returns the check characters appended to the data, in the order they are encoded.
+    pub fn check_chars(&self) -> Vec<char> {
+        let (c, k) = match self.check {
+            Code11Check::None => (false, false),
+            Code11Check::C => (true, false),
+            Code11Check::CK => (true, true),
+            // K-checksum is only appended on barcodes greater than 10 characters.
+            Code11Check::Auto => (true, self.chars.len() > 10),
+        };
+        let mut chars = vec![];
+
+        if c {
+            let c_checksum = self.c_checksum_char().expect("Cannot compute checksum C");
+            chars.push(c_checksum);
+
+            if k {
+                chars.push(self.k_checksum_char(c_checksum).expect("Cannot compute checksum K"));
+            }
+        }
+
+        chars
+    }
+
     fn char_encoding(&self, c: char) -> &[u8] {
         match CHARS.iter().find(|&ch| ch.0 == c) {
             Some(&(_, enc)) => enc,
-            None => panic!(format!("Unknown char: {}", c)),
+            None => panic!("Unknown char: {}", c),
         }
     }
 
@@ -65,21 +129,18 @@ impl Code11 {
         // checksum should use modulo-9. But most generators always use modulo-11.
         // This algorithm currently just uses 11 for both checksums, but can be easily 
         // changed at a later date.
-        match CHARS.get(index % CHARS.len()) {
-            Some(&(c, _)) => Some(c),
-            None => None,
-        }
+        CHARS.get(index % CHARS.len()).map(|&(c, _)| c)
     }
 
 
     /// Calculates the C checksum character using a weighted modulo-11 algorithm.
     fn c_checksum_char(&self) -> Option<char> {
-        self.checksum_char(&self.0, 10)
+        self.checksum_char(&self.chars, 10)
     }
 
     /// Calculates the K checksum character using a weighted modulo-11 algorithm.
     fn k_checksum_char(&self, c_checksum: char) -> Option<char> {
-        let mut data: Vec<char> = self.0.clone();
+        let mut data: Vec<char> = self.chars.clone();
         data.push(c_checksum);
 
         self.checksum_char(&data, 9)
@@ -92,21 +153,11 @@ impl Code11 {
 
     fn payload(&self) -> Vec<u8> {
         let mut enc = vec![];
-        let c_checksum = self.c_checksum_char().expect("Cannot compute checksum C");
 
-        for &c in &self.0 {
+        for &c in self.chars.iter().chain(self.check_chars().iter()) {
             self.push_encoding(&mut enc, self.char_encoding(c));
         }
 
-        self.push_encoding(&mut enc, self.char_encoding(c_checksum));
-
-        // K-checksum is only appended on barcodes greater than 10 characters.
-        if self.0.len() > 10 {
-            let k_checksum = self.k_checksum_char(c_checksum).expect("Cannot compute checksum K");
-
-            self.push_encoding(&mut enc, self.char_encoding(k_checksum));
-        }
-
         enc
     }
 
@@ -119,6 +170,12 @@ impl Code11 {
                              &self.payload()[..],
                              guard][..])
     }
+
+    /// Encodes the barcode using the given wide-to-narrow ratio (the default is 2:1).
+    /// Returns a Vec<u8> of encoded binary digits.
+    pub fn encode_with_ratio(&self, ratio: Ratio) -> Vec<u8> {
+        helpers::apply_ratio(&self.encode()[..], ratio)
+    }
 }
 
 impl Parse for Code11 {
@@ -135,9 +192,31 @@ impl Parse for Code11 {
     }
 }
 
+impl Barcode for Code11 {
+    fn encode(&self) -> Vec<u8> {
+        Code11::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "Code11"
+    }
+
+    fn data(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        match self.check_chars() {
+            ref c if c.is_empty() => None,
+            c => Some(c.into_iter().collect()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::code11::*;
+    use sym::{Barcode, Ratio};
     use error::Error;
     use std::char;
 
@@ -177,4 +256,40 @@ mod tests {
 
         assert_eq!(collapse_vec(code111.encode()), "101100101101011010010110110010101011011010110101101101010011010101001101101001010110101011011011001010100101101101011011011010100110101011001");
     }
+
+    #[test]
+    fn code11_as_barcode() {
+        let code111 = Code11::new("123-45").unwrap();
+        let code112 = Code11::new("1234-5678-4321").unwrap();
+
+        assert_eq!(code111.symbology(), "Code11");
+        assert_eq!(code111.checksum(), Some("5".to_owned()));
+        assert_eq!(code112.checksum(), Some("56".to_owned()));
+        assert_eq!(code112.text(), "1234-5678-432156");
+    }
+
+    #[test]
+    fn code11_encode_with_ratio() {
+        let code11 = Code11::new("1").unwrap();
+
+        assert_eq!(code11.encode_with_ratio(Ratio::new(4).unwrap()), code11.encode());
+        assert_eq!(collapse_vec(code11.encode_with_ratio(Ratio::new(6).unwrap())), "101110001011101011101110101110101110001");
+    }
+
+    #[test]
+    fn code11_with_options() {
+        let options = |check| Code11Options { check };
+        let none = Code11::with_options("123-45", options(Code11Check::None)).unwrap();
+        let c = Code11::with_options("1234-5678-4321", options(Code11Check::C)).unwrap();
+        let ck = Code11::with_options("123-45", options(Code11Check::CK)).unwrap();
+
+        assert_eq!(none.check_chars(), vec![]);
+        assert_eq!(none.checksum(), None);
+        assert_eq!(collapse_vec(none.encode()), "10110010110101101001011011001010101101010110110110110101011001");
+        assert_eq!(c.check_chars(), vec!['5']);
+        assert_eq!(c.text(), "1234-5678-43215");
+        assert_eq!(ck.check_chars(), vec!['5', '2']);
+        assert_eq!(ck.text(), "123-4552");
+        assert_eq!(Code11::new("1234-5678-4321").unwrap().check_chars(), vec!['5', '6']);
+    }
 }
@@ -51,11 +51,32 @@
 //! - FNC3: ```Ż``` (```\u{017B}```)
 //! - FNC4: ```ż``` (```\u{017C}```)
 //! - SHIFT: ```Ž``` (```\u{017D}```)
+//!
+//! ## Automatic character-set selection
+//!
+//! Alternatively, ```Code128::auto``` accepts plain text and chooses the character-sets for you,
+//! producing the shortest possible barcode. Digits are encoded in character-set C where that
+//! saves space, control characters and Latin-1 characters (via FNC4) are supported, and
+//! FNC1 - FNC3 may be given using the characters above:
+//!
+//! <ul><li>HELLO1234567</li></ul>
+//!
+//! Is encoded as:
+//!
+//! <ul><li>ƁHELLO1Ć234567</li></ul>
 
-use sym::helpers;
+use sym::{Barcode, Parse, helpers};
 use error::*;
+use std::ops::Range;
 
 use std::cmp;
+use std::char;
+
+// The characters accepted by Code128::auto that are not plain Latin-1 (FNC1, FNC2 and FNC3).
+const FNC_CHARS: [char; 3] = ['\u{0179}', '\u{017A}', '\u{017B}'];
+
+// The character-sets considered when optimising, in order of preference.
+const SETS: [CharacterSet; 3] = [CharacterSet::B, CharacterSet::A, CharacterSet::C];
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Unit {
@@ -66,6 +87,15 @@ enum Unit {
 
 type Encoding = [u8; 11];
 
+// A step of the character-set search: the units it appends, the total number of units of the
+// encoding it completes, and the (position, character-set) of the step it follows.
+#[derive(Clone, Debug)]
+struct Step {
+    len: usize,
+    prev: Option<(usize, usize)>,
+    units: Vec<Unit>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum CharacterSet {
     A,
@@ -150,6 +180,18 @@ impl Unit {
             Unit::C(n) => n,
         }
     }
+
+    // Returns the data syntax (as accepted by Code128::new) for this unit.
+    fn symbol(&self) -> &'static str {
+        let p = match *self {
+            Unit::A(_) => 0,
+            Unit::B(_) => 1,
+            Unit::C(_) => 2,
+        };
+        let s = CHARS[self.index()].0[p];
+
+        s.trim_start_matches("START-")
+    }
 }
 
 impl CharacterSet {
@@ -162,6 +204,15 @@ impl CharacterSet {
         }
     }
 
+    fn to_char(self) -> Result<char> {
+        match self {
+            CharacterSet::A => Ok('À'),
+            CharacterSet::B => Ok('Ɓ'),
+            CharacterSet::C => Ok('Ć'),
+            CharacterSet::None => Err(Error::Character),
+        }
+    }
+
     fn unit(self, n: usize) -> Result<Unit> {
         match self {
             CharacterSet::A => Ok(Unit::A(n)),
@@ -188,6 +239,43 @@ impl CharacterSet {
             None => Err(Error::Character),
         }
     }
+
+    // Looks up a plain character, accounting for DEL being represented as '÷' in CHARS.
+    fn lookup_char(self, c: char) -> Option<Unit> {
+        match c {
+            '\u{007F}' => self.lookup("\u{00F7}").ok(),
+            _ => self.lookup(&c.to_string()).ok(),
+        }
+    }
+
+    // Encodes the leading character (or pair of digits) of the given text in this
+    // character-set. Returns the number of characters consumed along with the units.
+    fn encode_text(self, text: &[char]) -> Option<(usize, Vec<Unit>)> {
+        let c = text[0];
+
+        match self {
+            CharacterSet::C if c.is_ascii_digit() => {
+                match text.get(1) {
+                    Some(d) if d.is_ascii_digit() => {
+                        let u = self.lookup(&format!("{}{}", c, d)).ok()?;
+                        Some((2, vec![u]))
+                    },
+                    _ => None,
+                }
+            },
+            _ if FNC_CHARS.contains(&c) => self.lookup_char(c).map(|u| (1, vec![u])),
+            CharacterSet::C => None,
+            _ if (c as u32) < 128 => self.lookup_char(c).map(|u| (1, vec![u])),
+            _ => {
+                // Latin-1 characters are encoded as FNC4 followed by the character - 128.
+                let base = char::from_u32(c as u32 - 128)?;
+                let fnc4 = self.lookup("\u{017C}").ok()?;
+                let u = self.lookup_char(base)?;
+
+                Some((1, vec![fnc4, u]))
+            },
+        }
+    }
 }
 
 impl Code128 {
@@ -199,9 +287,109 @@ impl Code128 {
             return Err(Error::Length);
         }
 
-        Code128::parse(data.chars().collect()).and_then(|u| {
-            Ok(Code128(u))
-        })
+        Code128::parse(data.chars().collect()).map(Code128)
+    }
+
+    /// Creates a new barcode from plain text, choosing the character-sets automatically.
+    /// Returns Result<Code128, Error> indicating parse success.
+    pub fn auto<T: AsRef<str>>(data: T) -> Result<Code128> {
+        let chars: Vec<char> = data.as_ref().chars().collect();
+        let valid_len = Code128::valid_len();
+        let valid_chars = Code128::valid_chars();
+
+        if (chars.len() as u32) < valid_len.start || (chars.len() as u32) > valid_len.end {
+            return Err(Error::Length);
+        }
+
+        if chars.iter().any(|c| !valid_chars.contains(c)) {
+            return Err(Error::Character);
+        }
+
+        Code128::optimise(&chars).map(Code128)
+    }
+
+    // Computes the shortest sequence of units (including START, CODE and SHIFT symbols) that
+    // encodes the given text. best[i][s] holds the last step of the shortest encoding of the first
+    // i characters that finishes in character-set SETS[s].
+    fn optimise(chars: &[char]) -> Result<Vec<Unit>> {
+        let mut best: Vec<[Option<Step>; 3]> = vec![[None, None, None]; chars.len() + 1];
+
+        for (s, set) in SETS.iter().enumerate() {
+            let start = format!("START-{}", set.to_char()?);
+            best[0][s] = Some(Step { len: 1, prev: None, units: vec![set.lookup(&start)?] });
+        }
+
+        for i in 0..=chars.len() {
+            // Switch character-set.
+            for (t, to) in SETS.iter().enumerate() {
+                for (s, from) in SETS.iter().enumerate() {
+                    if let (true, Some(len)) = (s != t, best[i][s].as_ref().map(|b| b.len)) {
+                        let units = vec![from.lookup(&to.to_char()?.to_string())?];
+                        Code128::relax(&mut best[i][t], len, (i, s), units);
+                    }
+                }
+            }
+
+            if i == chars.len() {
+                break;
+            }
+
+            for (s, set) in SETS.iter().enumerate() {
+                let len = match best[i][s] {
+                    Some(ref b) => b.len,
+                    None => continue,
+                };
+
+                // Encode in the current character-set.
+                if let Some((n, encoded)) = set.encode_text(&chars[i..]) {
+                    Code128::relax(&mut best[i + n][s], len, (i, s), encoded);
+                }
+
+                // SHIFT a single character between character-sets A and B.
+                let shifted = match *set {
+                    CharacterSet::A => CharacterSet::B,
+                    CharacterSet::B => CharacterSet::A,
+                    _ => continue,
+                };
+
+                if let Some((1, encoded)) = shifted.encode_text(&chars[i..]) {
+                    if encoded.len() == 1 {
+                        let mut units = vec![set.lookup("\u{017D}")?];
+                        units.extend(encoded);
+                        Code128::relax(&mut best[i + 1][s], len, (i, s), units);
+                    }
+                }
+            }
+        }
+
+        let shortest = best[chars.len()].iter()
+                                        .enumerate()
+                                        .filter_map(|(s, b)| b.as_ref().map(|b| (b.len, s)))
+                                        .min();
+        let mut position = match shortest {
+            Some((_, s)) => Some((chars.len(), s)),
+            None => return Err(Error::Character),
+        };
+        let mut steps = vec![];
+
+        // Follow the steps back to the START symbol.
+        while let Some((i, s)) = position {
+            let step = best[i][s].take().ok_or(Error::Character)?;
+            position = step.prev;
+            steps.push(step.units);
+        }
+
+        Ok(steps.into_iter().rev().flatten().collect())
+    }
+
+    // Replaces the current best step if the candidate, which appends the given units to the
+    // encoding of the given length at the previous position, is shorter.
+    fn relax(best: &mut Option<Step>, len: usize, prev: (usize, usize), units: Vec<Unit>) {
+        let len = len + units.len();
+
+        if best.as_ref().map_or(true, |b| len < b.len) {
+            *best = Some(Step { len, prev: Some(prev), units });
+        }
     }
 
     // Tokenizes and collects the data into the appropriate character-sets.
@@ -229,7 +417,7 @@ impl Code128 {
                         char_set = CharacterSet::from_char(ch)?;
                     }
                 },
-                d if d.is_digit(10) && char_set == CharacterSet::C => {
+                d if d.is_ascii_digit() && char_set == CharacterSet::C => {
                     match carry {
                         None => carry = Some(d),
                         Some(n) => {
@@ -275,7 +463,7 @@ impl Code128 {
     fn payload(&self) -> Vec<u8> {
         let slices: Vec<Encoding> = self.0
                                         .iter()
-                                        .map(|u| self.unit_encoding(&u))
+                                        .map(|u| self.unit_encoding(u))
                                         .collect();
 
         helpers::join_iters(slices.iter())
@@ -291,9 +479,66 @@ impl Code128 {
     }
 }
 
+impl Barcode for Code128 {
+    fn encode(&self) -> Vec<u8> {
+        Code128::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "Code128"
+    }
+
+    fn data(&self) -> String {
+        self.0.iter().map(|u| u.symbol()).collect()
+    }
+
+    // The check symbol has no character representation, so its value is returned.
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum_value().to_string())
+    }
+
+    // Omits the START, CODE and function characters, and applies FNC4 to the character
+    // that follows it.
+    fn text(&self) -> String {
+        let mut text = String::new();
+        let mut offset = 0;
+
+        for u in &self.0 {
+            match u.symbol() {
+                "\u{017C}" => offset = 128,
+                "À" | "Ɓ" | "Ć" | "\u{0179}" | "\u{017A}" | "\u{017B}" | "\u{017D}" => (),
+                s => {
+                    let chars = s.chars()
+                                 .map(|c| if c == '\u{00F7}' { '\u{007F}' } else { c })
+                                 .filter_map(|c| char::from_u32(c as u32 + offset))
+                                 .filter(|c| !c.is_control());
+
+                    text.extend(chars);
+                    offset = 0;
+                },
+            }
+        }
+
+        text
+    }
+}
+
+impl Parse for Code128 {
+    /// Returns the valid length of data acceptable by `Code128::auto`, in characters.
+    fn valid_len() -> Range<u32> {
+        1..256
+    }
+
+    /// Returns the set of valid characters accepted by `Code128::auto`: Latin-1 and FNC1 - FNC3.
+    fn valid_chars() -> Vec<char> {
+        (0..256u32).filter_map(char::from_u32).chain(FNC_CHARS.iter().cloned()).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::code128::*;
+    use sym::Barcode;
     use error::Error;
     use std::char;
 
@@ -364,4 +609,58 @@ mod tests {
         assert_eq!(collapse_vec(code128_b.encode()), "110100001001110001011011101101000101110111101101110010010111011110100111011001100011101011");
         assert_eq!(collapse_vec(code128_c.encode()), "1101001000011110010010110110111101110110001011101011110100111001101110010110011100101100110011011001100100010010011100110100101111001100011101011");
     }
+
+    #[test]
+    fn code128_as_barcode() {
+        let code128 = Code128::new("ÀXYĆ2199").unwrap();
+
+        assert_eq!(code128.symbology(), "Code128");
+        assert_eq!(code128.data(), "ÀXYĆ2199");
+        assert_eq!(code128.text(), "XY2199");
+        assert_eq!(code128.checksum(), Some("16".to_owned()));
+    }
+
+    #[test]
+    fn code128_auto() {
+        let code128_a = Code128::auto("HELLO").unwrap();
+        let code128_b = Code128::auto("1234").unwrap();
+        let code128_c = Code128::auto("HELLO1234567").unwrap();
+        let code128_d = Code128::auto("B\u{0006}").unwrap();
+        let code128_e = Code128::auto("Ź4218402050").unwrap();
+
+        assert_eq!(code128_a.encode(), Code128::new("ƁHELLO").unwrap().encode());
+        assert_eq!(code128_b.encode(), Code128::new("Ć1234").unwrap().encode());
+        assert_eq!(code128_c.encode(), Code128::new("ƁHELLO1Ć234567").unwrap().encode());
+        assert_eq!(code128_d.encode(), Code128::new("ÀB\u{0006}").unwrap().encode());
+        assert_eq!(code128_e.encode(), Code128::new("ĆŹ4218402050").unwrap().encode());
+    }
+
+    #[test]
+    fn code128_auto_shift() {
+        let code128_a = Code128::auto("a\u{0001}b").unwrap();
+        let code128_b = Code128::auto("12345a").unwrap();
+
+        assert_eq!(code128_a.0, vec![Unit::B(104), Unit::B(65), Unit::B(98), Unit::A(65), Unit::B(66)]);
+        assert_eq!(code128_b.0, vec![Unit::C(105), Unit::C(12), Unit::C(34), Unit::C(100), Unit::B(21), Unit::B(65)]);
+    }
+
+    #[test]
+    fn code128_auto_latin1() {
+        let code128_a = Code128::auto("éa").unwrap();
+
+        assert_eq!(code128_a.encode(), Code128::new("Ɓ\u{017C}ia").unwrap().encode());
+        assert_eq!(code128_a.text(), "éa");
+    }
+
+    #[test]
+    fn invalid_data_code128_auto() {
+        let code128_a = Code128::auto("");
+        let code128_b = Code128::auto("☺");
+        let code128_c = Code128::auto("1".repeat(257));
+
+        assert_eq!(code128_a.err().unwrap(), Error::Length);
+        assert_eq!(code128_b.err().unwrap(), Error::Character);
+        assert_eq!(code128_c.err().unwrap(), Error::Length);
+        assert!(Code128::auto("é".repeat(256)).is_ok());
+    }
 }
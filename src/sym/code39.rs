@@ -5,9 +5,12 @@
 //! Code39 is the standard barcode used by the United States Department of Defense and is also
 //! popular in non-retail environments. It was one of the first symbologies to support encoding
 //! of the ASCII alphabet.
+//!
+//! In Full ASCII (extended) mode, all 128 ASCII characters can be encoded. Characters outside of
+//! the standard character set are encoded as a pair of symbols, using $, %, / or + as a shift.
 
-use sym::{Parse, helpers};
-use error::Result;
+use sym::{Barcode, Parse, Ratio, helpers};
+use error::{Error, Result};
 use std::ops::Range;
 
 // Character -> Binary mappings for each of the 43 allowable character.
@@ -29,6 +32,18 @@ const CHARS: [(char, [u8; 12]); 43] = [
     ('%', [1,0,1,0,0,1,0,0,1,0,0,1]),
 ];
 
+// ASCII -> Full ASCII symbol mappings for each of the 128 ASCII characters.
+const FULL_ASCII: [&str; 128] = [
+    "%U", "$A", "$B", "$C", "$D", "$E", "$F", "$G", "$H", "$I", "$J", "$K", "$L", "$M", "$N", "$O",
+    "$P", "$Q", "$R", "$S", "$T", "$U", "$V", "$W", "$X", "$Y", "$Z", "%A", "%B", "%C", "%D", "%E",
+    " ", "/A", "/B", "/C", "/D", "/E", "/F", "/G", "/H", "/I", "/J", "/K", "/L", "-", ".", "/O",
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "/Z", "%F", "%G", "%H", "%I", "%J",
+    "%V", "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O",
+    "P", "Q", "R", "S", "T", "U", "V", "W", "X", "Y", "Z", "%K", "%L", "%M", "%N", "%O",
+    "%W", "+A", "+B", "+C", "+D", "+E", "+F", "+G", "+H", "+I", "+J", "+K", "+L", "+M", "+N", "+O",
+    "+P", "+Q", "+R", "+S", "+T", "+U", "+V", "+W", "+X", "+Y", "+Z", "%P", "%Q", "%R", "%S", "%T",
+];
+
 // Code39 barcodes must start and end with the '*' special character.
 const GUARD: [u8; 12] = [1,0,0,1,0,1,1,0,1,1,0,1];
 
@@ -36,42 +51,91 @@ const GUARD: [u8; 12] = [1,0,0,1,0,1,1,0,1,1,0,1];
 #[derive(Debug)]
 pub struct Code39 {
     data: Vec<char>,
+    full_ascii: bool,
     /// Indicates whether to encode a checksum digit.
     pub checksum: bool,
 }
 
 impl Code39 {
-    fn init(data: &str, checksum: bool) -> Result<Code39> {
-        Code39::parse(data).and_then(|d| {
-            Ok(Code39 {
-                data: d.chars().collect(),
-                checksum,
-            })
+    fn init(data: &str, checksum: bool, full_ascii: bool) -> Result<Code39> {
+        let data = if full_ascii {
+            Code39::parse_full_ascii(data)?
+        } else {
+            Code39::parse(data)?
+        };
+
+        Ok(Code39 {
+            data: data.chars().collect(),
+            full_ascii,
+            checksum,
         })
     }
 
     /// Creates a new barcode.
     /// Returns Result<Code39, Error> indicating parse success.
     pub fn new<T: AsRef<str>>(data: T) -> Result<Code39> {
-        Code39::init(data.as_ref(), false)
+        Code39::init(data.as_ref(), false, false)
     }
 
     /// Creates a new barcode with an appended check-digit, calculated using modulo-43..
     /// Returns Result<Code39, Error> indicating parse success.
     pub fn with_checksum<T: AsRef<str>>(data: T) -> Result<Code39> {
-        Code39::init(data.as_ref(), true)
+        Code39::init(data.as_ref(), true, false)
+    }
+
+    /// Creates a new Full ASCII barcode, which can encode any ASCII character.
+    /// Returns Result<Code39, Error> indicating parse success.
+    pub fn full_ascii<T: AsRef<str>>(data: T) -> Result<Code39> {
+        Code39::init(data.as_ref(), false, true)
+    }
+
+    /// Creates a new Full ASCII barcode with an appended check-digit, calculated over the
+    /// expanded symbols using modulo-43.
+    /// Returns Result<Code39, Error> indicating parse success.
+    pub fn full_ascii_with_checksum<T: AsRef<str>>(data: T) -> Result<Code39> {
+        Code39::init(data.as_ref(), true, true)
+    }
+
+    fn parse_full_ascii(data: &str) -> Result<&str> {
+        let valid_len = Code39::valid_len();
+        let data_len = data.len() as u32;
+
+        if data_len < valid_len.start || data_len > valid_len.end {
+            return Err(Error::Length);
+        }
+
+        if data.is_ascii() {
+            Ok(data)
+        } else {
+            Err(Error::Character)
+        }
+    }
+
+    /// Returns the symbols that are encoded, excluding the checksum and the '*' guards.
+    /// In Full ASCII mode, this is the data with each character expanded to its shift pair.
+    pub fn symbols(&self) -> String {
+        self.expand().into_iter().collect()
+    }
+
+    fn expand(&self) -> Vec<char> {
+        if !self.full_ascii {
+            return self.data.clone();
+        }
+
+        self.data
+            .iter()
+            .flat_map(|&c| FULL_ASCII[c as usize].chars())
+            .collect()
     }
 
     /// Calculates the checksum character using a modulo-43 algorithm.
     fn checksum_char(&self) -> Option<char> {
         let get_char_pos = |&c| CHARS.iter().position(|t| t.0 == c).unwrap();
-        let indices = self.data.iter().map(&get_char_pos);
+        let symbols = self.expand();
+        let indices = symbols.iter().map(&get_char_pos);
         let index = indices.sum::<usize>() % CHARS.len();
 
-        match CHARS.get(index) {
-            Some(&(c, _)) => Some(c),
-            None => None,
-        }
+        CHARS.get(index).map(|&(c, _)| c)
     }
 
     fn checksum_encoding(&self) -> [u8; 12] {
@@ -84,7 +148,7 @@ impl Code39 {
     fn char_encoding(&self, c: char) -> [u8; 12] {
         match CHARS.iter().find(|&ch| ch.0 == c) {
             Some(&(_, enc)) => enc,
-            None => panic!(format!("Unknown char: {}", c)),
+            None => panic!("Unknown char: {}", c),
         }
     }
 
@@ -98,7 +162,7 @@ impl Code39 {
     fn payload(&self) -> Vec<u8> {
         let mut enc = vec![0];
 
-        for c in &self.data {
+        for c in &self.expand() {
             self.push_encoding(&mut enc, self.char_encoding(*c));
         }
 
@@ -116,6 +180,12 @@ impl Code39 {
 
         helpers::join_slices(&[guard, &self.payload()[..], guard][..])
     }
+
+    /// Encodes the barcode using the given wide-to-narrow ratio (the default is 2:1).
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode_with_ratio(&self, ratio: Ratio) -> Vec<u8> {
+        helpers::apply_ratio(&self.encode()[..], ratio)
+    }
 }
 
 impl Parse for Code39 {
@@ -129,9 +199,32 @@ impl Parse for Code39 {
     }
 }
 
+impl Barcode for Code39 {
+    fn encode(&self) -> Vec<u8> {
+        Code39::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "Code39"
+    }
+
+    fn data(&self) -> String {
+        self.data.iter().collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        if self.checksum {
+            self.checksum_char().map(|c| c.to_string())
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::code39::*;
+    use sym::{Barcode, Ratio};
     use error::Error;
     use std::char;
 
@@ -180,4 +273,67 @@ mod tests {
         assert_eq!(collapse_vec(code391.encode()), "100101101101011010010101101011001010110110110010101010100110101101101010010110100101101101");
         assert_eq!(collapse_vec(code392.encode()), "1001011011010101100101101011010010110101101100101010110101011001010101100101101101001101010110100101011010110010101101011011010010100101101101");
     }
+
+    #[test]
+    fn code39_as_barcode() {
+        let code391 = Code39::new("1234").unwrap();
+        let code392 = Code39::with_checksum("1234").unwrap();
+
+        assert_eq!(code391.symbology(), "Code39");
+        assert_eq!(code391.checksum(), None);
+        assert_eq!(code391.text(), "1234");
+        assert_eq!(code392.checksum(), Some("A".to_owned()));
+        assert_eq!(code392.text(), "1234A");
+    }
+
+    #[test]
+    fn code39_encode_with_ratio() {
+        let code39 = Code39::new("A").unwrap();
+
+        assert_eq!(code39.encode_with_ratio(Ratio::new(4).unwrap()), code39.encode());
+        assert_eq!(collapse_vec(code39.encode_with_ratio(Ratio::new(6).unwrap())), "10001011101110101110101000101110100010111011101");
+        assert_eq!(code39.encode_with_ratio(Ratio::new(5).unwrap()).len(), 85);
+    }
+
+    #[test]
+    fn new_code39_full_ascii() {
+        let code39 = Code39::full_ascii("Asset tag #12");
+
+        assert!(code39.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_code39_full_ascii() {
+        let code39 = Code39::full_ascii("Café");
+
+        assert_eq!(code39.err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn code39_full_ascii_symbols() {
+        let code391 = Code39::full_ascii("Code39").unwrap();
+        let code392 = Code39::full_ascii("\u{0}\r\n~\u{7F} *").unwrap();
+
+        assert_eq!(code391.symbols(), "C+O+D+E39");
+        assert_eq!(code392.symbols(), "%U$M$J%S%T /J");
+        assert_eq!(Code39::new("CODE39").unwrap().symbols(), "CODE39");
+    }
+
+    #[test]
+    fn code39_full_ascii_encode() {
+        let code391 = Code39::full_ascii("a+").unwrap();
+        let code392 = Code39::full_ascii("A1").unwrap();
+
+        assert_eq!(code391.encode(), Code39::new("+A/K").unwrap().encode());
+        assert_eq!(code392.encode(), Code39::new("A1").unwrap().encode());
+    }
+
+    #[test]
+    fn code39_full_ascii_with_checksum() {
+        let code39 = Code39::full_ascii_with_checksum("a+").unwrap();
+
+        assert_eq!(code39.checksum(), Some("P".to_owned()));
+        assert_eq!(code39.data(), "a+");
+        assert_eq!(code39.encode(), Code39::with_checksum("+A/K").unwrap().encode());
+    }
 }
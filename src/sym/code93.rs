@@ -5,15 +5,15 @@
 //!
 //! Code93 is a continuous, variable-length symbology.
 //!
-//! NOTE: This encoder currently only supports the basic Code93 implementation and not full-ASCII
-//! mode.
+//! In Full ASCII mode, all 128 ASCII characters can be encoded. Characters outside of the basic
+//! character set are encoded as a pair of symbols, using one of the four dedicated shift symbols.
 
-use sym::{Parse, helpers};
-use error::Result;
+use sym::{Barcode, Parse, helpers};
+use error::{Error, Result};
 use std::ops::Range;
 
 // Character -> Binary mappings for each of the 47 allowable character.
-// The special "full-ASCII" characters are represented with (, ), [, ].
+// The special "full-ASCII" shift characters ($), (%), (/), (+) are represented with (, ), [, ].
 const CHARS: [(char, [u8; 9]); 47] = [
     ('0', [1,0,0,0,1,0,1,0,0]), ('1', [1,0,1,0,0,1,0,0,0]), ('2', [1,0,1,0,0,0,1,0,0]),
     ('3', [1,0,1,0,0,0,0,1,0]), ('4', [1,0,0,1,0,1,0,0,0]), ('5', [1,0,0,1,0,0,1,0,0]),
@@ -30,7 +30,22 @@ const CHARS: [(char, [u8; 9]); 47] = [
     ('-', [1,0,0,1,0,1,1,1,0]), ('.', [1,1,1,0,1,0,1,0,0]), (' ', [1,1,1,0,1,0,0,1,0]),
     ('$', [1,1,1,0,0,1,0,1,0]), ('/', [1,0,1,1,0,1,1,1,0]), ('+', [1,0,1,1,1,0,1,1,0]),
     ('%', [1,1,0,1,0,1,1,1,0]), ('(', [1,0,0,1,0,0,1,1,0]), (')', [1,1,1,0,1,1,0,1,0]),
-    ('[', [1,1,1,0,1,0,1,1,0]), ('[', [1,0,0,1,1,0,0,1,0]),
+    ('[', [1,1,1,0,1,0,1,1,0]), (']', [1,0,0,1,1,0,0,1,0]),
+];
+
+// The full-ASCII shift characters.
+const SHIFTS: [char; 4] = ['(', ')', '[', ']'];
+
+// ASCII -> Full ASCII symbol mappings for each of the 128 ASCII characters.
+const FULL_ASCII: [&str; 128] = [
+    ")U", "(A", "(B", "(C", "(D", "(E", "(F", "(G", "(H", "(I", "(J", "(K", "(L", "(M", "(N", "(O",
+    "(P", "(Q", "(R", "(S", "(T", "(U", "(V", "(W", "(X", "(Y", "(Z", ")A", ")B", ")C", ")D", ")E",
+    " ", "[A", "[B", "[C", "$", "%", "[F", "[G", "[H", "[I", "[J", "+", "[L", "-", ".", "/",
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "[Z", ")F", ")G", ")H", ")I", ")J",
+    ")V", "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O",
+    "P", "Q", "R", "S", "T", "U", "V", "W", "X", "Y", "Z", ")K", ")L", ")M", ")N", ")O",
+    ")W", "]A", "]B", "]C", "]D", "]E", "]F", "]G", "]H", "]I", "]J", "]K", "]L", "]M", "]N", "]O",
+    "]P", "]Q", "]R", "]S", "]T", "]U", "]V", "]W", "]X", "]Y", "]Z", ")P", ")Q", ")R", ")S", ")T",
 ];
 
 // Code93 barcodes must start and end with the '*' special character.
@@ -39,22 +54,67 @@ const TERMINATOR: [u8; 1] = [1];
 
 /// The Code93 barcode type.
 #[derive(Debug)]
-pub struct Code93(Vec<char>);
+pub struct Code93 {
+    symbols: Vec<char>,
+    full_ascii: bool,
+}
 
 impl Code93 {
     /// Creates a new barcode.
     /// Returns Result<Code93, Error> indicating parse success.
     pub fn new<T: AsRef<str>>(data: T) -> Result<Code93> {
-        Code93::parse(data.as_ref()).and_then(|d| {
-            Ok(Code93(d.chars()
-                       .collect()))
+        Code93::parse(data.as_ref()).map(|d| Code93 {
+            symbols: d.chars().collect(),
+            full_ascii: false,
         })
     }
 
+    /// Creates a new Full ASCII barcode, which can encode any ASCII character.
+    /// Returns Result<Code93, Error> indicating parse success.
+    pub fn full_ascii<T: AsRef<str>>(data: T) -> Result<Code93> {
+        let data = data.as_ref();
+        let valid_len = Code93::valid_len();
+        let data_len = data.len() as u32;
+
+        if data_len < valid_len.start || data_len > valid_len.end {
+            return Err(Error::Length);
+        }
+
+        if !data.is_ascii() {
+            return Err(Error::Character);
+        }
+
+        Ok(Code93 {
+            symbols: data.chars().flat_map(|c| FULL_ASCII[c as usize].chars()).collect(),
+            full_ascii: true,
+        })
+    }
+
+    /// Converts the Full ASCII symbols back into the ASCII characters they represent.
+    fn collapse(&self) -> String {
+        let mut data = String::new();
+        let mut symbols = self.symbols.iter();
+
+        while let Some(&c) = symbols.next() {
+            if !SHIFTS.contains(&c) {
+                data.push(c);
+                continue;
+            }
+
+            let pair: String = Some(&c).into_iter().chain(symbols.next()).collect();
+
+            if let Some(i) = FULL_ASCII.iter().position(|&s| s == pair) {
+                data.push(i as u8 as char);
+            }
+        }
+
+        data
+    }
+
     fn char_encoding(&self, c: char) -> [u8; 9] {
         match CHARS.iter().find(|&ch| ch.0 == c) {
             Some(&(_, enc)) => enc,
-            None => panic!(format!("Unknown char: {}", c)),
+            None => panic!("Unknown char: {}", c),
         }
     }
 
@@ -71,21 +131,18 @@ impl Code93 {
         let index = positions.enumerate()
                              .fold(0, |acc, (i, pos)| acc + (weight(i) * pos));
 
-        match CHARS.get(index % CHARS.len()) {
-            Some(&(c, _)) => Some(c),
-            None => None,
-        }
+        CHARS.get(index % CHARS.len()).map(|&(c, _)| c)
     }
 
 
     /// Calculates the C checksum character using a weighted modulo-47 algorithm.
     fn c_checksum_char(&self) -> Option<char> {
-        self.checksum_char(&self.0, 20)
+        self.checksum_char(&self.symbols, 20)
     }
 
     /// Calculates the K checksum character using a weighted modulo-47 algorithm.
     fn k_checksum_char(&self, c_checksum: char) -> Option<char> {
-        let mut data: Vec<char> = self.0.clone();
+        let mut data: Vec<char> = self.symbols.clone();
         data.push(c_checksum);
 
         self.checksum_char(&data, 15)
@@ -100,7 +157,7 @@ impl Code93 {
         let c_checksum = self.c_checksum_char().expect("Cannot compute checksum C");
         let k_checksum = self.k_checksum_char(c_checksum).expect("Cannot compute checksum K");
 
-        for &c in &self.0 {
+        for &c in &self.symbols {
             self.push_encoding(&mut enc, self.char_encoding(c));
         }
 
@@ -136,9 +193,40 @@ impl Parse for Code93 {
     }
 }
 
+impl Barcode for Code93 {
+    fn encode(&self) -> Vec<u8> {
+        Code93::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "Code93"
+    }
+
+    fn data(&self) -> String {
+        if self.full_ascii {
+            self.collapse()
+        } else {
+            self.symbols.iter().collect()
+        }
+    }
+
+    fn checksum(&self) -> Option<String> {
+        let c_checksum = self.c_checksum_char()?;
+        let k_checksum = self.k_checksum_char(c_checksum)?;
+
+        Some(format!("{}{}", c_checksum, k_checksum))
+    }
+
+    // Code93 check characters are not included in the human-readable text.
+    fn text(&self) -> String {
+        self.data()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::code93::*;
+    use sym::Barcode;
     use error::Error;
     use std::char;
 
@@ -174,4 +262,48 @@ mod tests {
         assert_eq!(collapse_vec(code933.encode()), "1010111101000010101000010101101100101000101101010111101");
         assert_eq!(collapse_vec(code934.encode()), "1010111101010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001010010001000101101110010101010111101");
     }
+
+    #[test]
+    fn code93_as_barcode() {
+        let code93 = Code93::new("TEST93").unwrap();
+
+        assert_eq!(code93.symbology(), "Code93");
+        assert_eq!(code93.data(), "TEST93");
+        assert_eq!(code93.checksum(), Some("+6".to_owned()));
+        assert_eq!(code93.text(), "TEST93");
+    }
+
+    #[test]
+    fn invalid_data_code93_full_ascii() {
+        let code93 = Code93::full_ascii("Über");
+
+        assert_eq!(code93.err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn code93_full_ascii_encode() {
+        let code931 = Code93::full_ascii("a$").unwrap();
+        let code932 = Code93::full_ascii("TEST93").unwrap();
+        let code933 = Code93::full_ascii("\r\n").unwrap();
+
+        assert_eq!(code931.encode(), Code93::new("]A$").unwrap().encode());
+        assert_eq!(code932.encode(), Code93::new("TEST93").unwrap().encode());
+        assert_eq!(code933.encode(), Code93::new("(M(J").unwrap().encode());
+    }
+
+    #[test]
+    fn code93_full_ascii_checksum() {
+        let code93 = Code93::full_ascii("Code93").unwrap();
+
+        assert_eq!(code93.checksum(), Code93::new("C]O]D]E93").unwrap().checksum());
+        assert_eq!(code93.text(), "Code93");
+    }
+
+    #[test]
+    fn code93_full_ascii_roundtrip() {
+        let ascii: String = (0u8..128).map(|b| b as char).collect();
+        let code93 = Code93::full_ascii(&ascii).unwrap();
+
+        assert_eq!(code93.data(), ascii);
+    }
 }
@@ -0,0 +1,715 @@
+//! Encoders for GS1 DataBar barcodes.
+//!
+//! GS1 DataBar (formerly Reduced Space Symbology, or RSS) encodes a GTIN-14 in less space than
+//! EAN/UPC barcodes, and is used on retail items such as fresh produce.
+//!
+//! GS1 DataBar Omnidirectional (DataBar-14) encodes the GTIN as four data characters and two
+//! finder patterns, whose values carry a modulo-79 checksum. It has the following variants:
+//!
+//! * Omnidirectional: a single row, 33 modules high, that can be read by omnidirectional
+//!   scanners.
+//! * Truncated: the same row, reduced to 13 modules high.
+//! * Stacked: the row split in two, 5 and 7 modules high, with a separator row between them.
+//! * Stacked Omnidirectional: the row split in two, each 33 modules high, with three separator
+//!   rows between them.
+//!
+//! As the variants differ in their rows and heights, barcodes are described by their `Layout`,
+//! whose relative row heights are in modules.
+//!
+//! GS1 DataBar Limited encodes GTINs starting with 0 or 1 as two data characters and a check
+//! character carrying a modulo-89 checksum, in a single row 10 modules high.
+
+use sym::{Barcode, Parse, helpers};
+use sym::layout::{Layout, Row};
+use error::{Error, Result};
+use std::ops::Range;
+use std::char;
+
+// The values of the data characters are split into groups, which determine the number of modules
+// and the widest element of the character's odd and even elements. The outside (1st and 3rd) and
+// inside (2nd and 4th) characters use different groups.
+const OUTSIDE_GROUPS: [Group; 5] = [
+    Group { start: 0, combinations: 1, odd: (12, 8), even: (4, 1) },
+    Group { start: 161, combinations: 10, odd: (10, 6), even: (6, 3) },
+    Group { start: 961, combinations: 34, odd: (8, 4), even: (8, 5) },
+    Group { start: 2015, combinations: 70, odd: (6, 3), even: (10, 6) },
+    Group { start: 2715, combinations: 126, odd: (4, 1), even: (12, 8) },
+];
+const INSIDE_GROUPS: [Group; 4] = [
+    Group { start: 0, combinations: 4, odd: (5, 2), even: (10, 7) },
+    Group { start: 336, combinations: 20, odd: (7, 4), even: (8, 5) },
+    Group { start: 1036, combinations: 48, odd: (9, 6), even: (6, 3) },
+    Group { start: 1516, combinations: 81, odd: (11, 8), even: (4, 1) },
+];
+const LIMITED_GROUPS: [Group; 7] = [
+    Group { start: 0, combinations: 28, odd: (17, 6), even: (9, 3) },
+    Group { start: 183_064, combinations: 728, odd: (13, 5), even: (13, 4) },
+    Group { start: 820_064, combinations: 6454, odd: (9, 3), even: (17, 6) },
+    Group { start: 1_000_776, combinations: 203, odd: (15, 5), even: (11, 4) },
+    Group { start: 1_491_021, combinations: 2408, odd: (11, 4), even: (15, 5) },
+    Group { start: 1_979_845, combinations: 1, odd: (19, 8), even: (7, 1) },
+    Group { start: 1_996_939, combinations: 16632, odd: (7, 1), even: (19, 8) },
+];
+
+// The element widths of the finder patterns.
+const FINDERS: [[u8; 5]; 9] = [
+    [3, 8, 2, 1, 1], [3, 5, 5, 1, 1], [3, 3, 7, 1, 1], [3, 1, 9, 1, 1], [2, 7, 4, 1, 1],
+    [2, 5, 6, 1, 1], [2, 3, 8, 1, 1], [1, 5, 7, 1, 1], [1, 3, 9, 1, 1],
+];
+
+// The element widths of the guard patterns. DataBar Limited has a wider right guard pattern.
+const GUARD: [u8; 2] = [1, 1];
+const LIMITED_RIGHT_GUARD: [u8; 3] = [1, 1, 5];
+
+// The number of elements in the left half of the barcode, which forms the top row of the
+// stacked variants.
+const LEFT_ELEMENTS: usize = 23;
+
+/// A group of data character values.
+#[derive(Copy, Clone, Debug)]
+struct Group {
+    /// The first value of the group.
+    start: u32,
+    /// The number of combinations of the elements that vary fastest with the value.
+    combinations: u32,
+    /// The total modules and the widest element of the odd elements.
+    odd: (u32, u32),
+    /// The total modules and the widest element of the even elements.
+    even: (u32, u32),
+}
+
+/// Calculates the number of combinations of `r` items from `n`.
+fn combinations(n: i64, r: i64) -> i64 {
+    if r < 0 || r > n {
+        return 0;
+    }
+
+    (0..r.min(n - r)).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Converts a value into the widths of `elements` elements spanning `modules` modules, with no
+/// element wider than `widest`. Unless `no_narrow` is set, at least one element is one module
+/// wide.
+fn value_widths(value: u32, modules: u32, elements: u32, widest: u32, no_narrow: bool) -> Vec<u8> {
+    let (mut value, mut modules) = (i64::from(value), i64::from(modules));
+    let (elements, widest) = (i64::from(elements), i64::from(widest));
+    let mut widths = vec![];
+    let mut narrow = false;
+
+    for bar in 0..elements - 1 {
+        let mut width = 1;
+
+        loop {
+            let remaining = elements - bar - 1;
+            let mut sub = combinations(modules - width - 1, remaining - 1);
+
+            if !no_narrow && !narrow && width > 1 && modules - width - remaining >= remaining {
+                sub -= combinations(modules - width - remaining - 1, remaining - 1);
+            }
+
+            if remaining > 1 {
+                let less: i64 = (widest + 1..=modules - width - remaining + 1)
+                    .map(|w| combinations(modules - width - w - 1, remaining - 2))
+                    .sum();
+
+                sub -= less * remaining;
+            } else if modules - width > widest {
+                sub -= 1;
+            }
+
+            if value < sub {
+                break;
+            }
+
+            value -= sub;
+            width += 1;
+        }
+
+        narrow = narrow || width == 1;
+        modules -= width;
+        widths.push(width as u8);
+    }
+
+    widths.push(modules as u8);
+    widths
+}
+
+/// Converts the value of a data character into the widths of its elements, alternating between
+/// the odd and even elements. `elements` is the number of odd (and even) elements.
+/// If `odd_slowest` is set, the odd elements vary slowest with the value and need not include a
+/// narrow element, otherwise the even elements do.
+fn character_widths(value: u32, groups: &[Group], elements: u32, odd_slowest: bool) -> Vec<u8> {
+    let group = groups.iter().rev().find(|g| value >= g.start).expect("Unknown value");
+    let value = value - group.start;
+    let (odd_value, even_value) = if odd_slowest {
+        (value / group.combinations, value % group.combinations)
+    } else {
+        (value % group.combinations, value / group.combinations)
+    };
+    let odd = value_widths(odd_value, group.odd.0, elements, group.odd.1, odd_slowest);
+    let even = value_widths(even_value, group.even.0, elements, group.even.1, !odd_slowest);
+
+    odd.iter().zip(even.iter()).flat_map(|(&o, &e)| vec![o, e]).collect()
+}
+
+/// Converts element widths into modules, starting with a space or a bar.
+fn modules(widths: &[u8], bar: bool) -> Vec<u8> {
+    let mut modules = vec![];
+
+    for (i, &w) in widths.iter().enumerate() {
+        let module = (i % 2 == 0) == bar;
+        modules.extend(vec![module as u8; w as usize]);
+    }
+
+    modules
+}
+
+/// The variants of GS1 DataBar Omnidirectional barcodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DataBarVariant {
+    /// A single row, 33 modules high.
+    Omnidirectional,
+    /// A single row, 13 modules high.
+    Truncated,
+    /// Two rows, 5 and 7 modules high, separated by a 1 module high separator row.
+    Stacked,
+    /// Two rows, each 33 modules high, separated by three 1 module high separator rows.
+    StackedOmnidirectional,
+}
+
+/// The GS1 DataBar Omnidirectional barcode type.
+#[derive(Debug)]
+pub struct DataBar {
+    digits: Vec<u8>,
+    variant: DataBarVariant,
+}
+
+impl DataBar {
+    /// Creates a new GS1 DataBar Omnidirectional barcode.
+    /// The data can either be the 13 digits of the GTIN without the check digit, or the full 14
+    /// digits including the check digit, in which case the check digit is verified.
+    /// Returns Result<DataBar, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<DataBar> {
+        DataBar::with_variant(data, DataBarVariant::Omnidirectional)
+    }
+
+    /// Creates a new GS1 DataBar Truncated barcode.
+    /// Returns Result<DataBar, Error> indicating parse success.
+    pub fn truncated<T: AsRef<str>>(data: T) -> Result<DataBar> {
+        DataBar::with_variant(data, DataBarVariant::Truncated)
+    }
+
+    /// Creates a new GS1 DataBar Stacked barcode.
+    /// Returns Result<DataBar, Error> indicating parse success.
+    pub fn stacked<T: AsRef<str>>(data: T) -> Result<DataBar> {
+        DataBar::with_variant(data, DataBarVariant::Stacked)
+    }
+
+    /// Creates a new GS1 DataBar Stacked Omnidirectional barcode.
+    /// Returns Result<DataBar, Error> indicating parse success.
+    pub fn stacked_omnidirectional<T: AsRef<str>>(data: T) -> Result<DataBar> {
+        DataBar::with_variant(data, DataBarVariant::StackedOmnidirectional)
+    }
+
+    /// Creates a new barcode of the given variant.
+    /// Returns Result<DataBar, Error> indicating parse success.
+    pub fn with_variant<T: AsRef<str>>(data: T, variant: DataBarVariant) -> Result<DataBar> {
+        DataBar::parse(data.as_ref()).and_then(|d| {
+            let mut digits: Vec<u8> = d.chars()
+                                       .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                                       .collect();
+            let check = if digits.len() == 14 { digits.pop() } else { None };
+            let databar = DataBar { digits, variant };
+
+            match check {
+                Some(c) if c != databar.checksum_digit() => Err(Error::Checksum),
+                _ => Ok(databar),
+            }
+        })
+    }
+
+    /// Returns the variant of the barcode.
+    pub fn variant(&self) -> DataBarVariant {
+        self.variant
+    }
+
+    /// Returns the GTIN-14 encoded in the barcode, including the check digit.
+    pub fn gtin(&self) -> String {
+        self.digits
+            .iter()
+            .chain(Some(self.checksum_digit()).iter())
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    /// Calculates the GTIN check digit using a modulo-10 weighting algorithm.
+    fn checksum_digit(&self) -> u8 {
+        helpers::modulo_10_checksum(&self.digits[..], false)
+    }
+
+    /// Calculates the element widths of the four data characters.
+    fn data_characters(&self) -> Vec<Vec<u8>> {
+        let value = self.digits.iter().fold(0u64, |acc, &d| acc * 10 + u64::from(d));
+        let (left, right) = ((value / 4_537_077) as u32, (value % 4_537_077) as u32);
+
+        // The odd elements vary slowest in the outside characters, and the even elements in the
+        // inside characters.
+        vec![character_widths(left / 1597, &OUTSIDE_GROUPS[..], 4, true),
+             character_widths(left % 1597, &INSIDE_GROUPS[..], 4, false),
+             character_widths(right / 1597, &OUTSIDE_GROUPS[..], 4, true),
+             character_widths(right % 1597, &INSIDE_GROUPS[..], 4, false)]
+    }
+
+    /// Calculates the modulo-79 checksum of the data characters, as the values of the left and
+    /// right finder patterns.
+    fn finders(characters: &[Vec<u8>]) -> (usize, usize) {
+        // The weight of each element is the next power of 3, modulo 79.
+        let mut weight = 1;
+        let mut checksum = 0;
+
+        for &w in characters.iter().flat_map(|c| c.iter()) {
+            checksum = (checksum + weight * u32::from(w)) % 79;
+            weight = weight * 3 % 79;
+        }
+
+        // The finder combinations (0, 8) and (8, 0) are not used.
+        if checksum >= 8 {
+            checksum += 1;
+        }
+        if checksum >= 72 {
+            checksum += 1;
+        }
+
+        (checksum as usize / 9, checksum as usize % 9)
+    }
+
+    /// Returns the widths of the 46 elements of the barcode, from left to right, starting with a
+    /// space.
+    fn element_widths(&self) -> Vec<u8> {
+        let characters = self.data_characters();
+        let (left, right) = DataBar::finders(&characters[..]);
+        let mut widths = GUARD.to_vec();
+
+        widths.extend(&characters[0]);
+        widths.extend(&FINDERS[left]);
+        widths.extend(characters[1].iter().rev());
+        widths.extend(&characters[3]);
+        widths.extend(FINDERS[right].iter().rev());
+        widths.extend(characters[2].iter().rev());
+        widths.extend(&GUARD);
+        widths
+    }
+
+    /// Encodes the barcode as a single row.
+    /// Returns a Vec<u8> of encoded binary digits. The stacked variants split these into two
+    /// rows, see `layout`.
+    pub fn encode(&self) -> Vec<u8> {
+        modules(&self.element_widths()[..], false)
+    }
+
+    /// Generates the separator row of a Stacked barcode. Where the modules above and below are
+    /// the same, the separator is their complement, otherwise it alternates.
+    fn stacked_separator(top: &[u8], bottom: &[u8]) -> Vec<u8> {
+        let mut separator = vec![0; top.len()];
+
+        for i in 1..top.len() - 4 {
+            separator[i] = match (top[i], bottom[i]) {
+                (0, 0) => 1,
+                (1, 1) => 0,
+                _ => 1 - separator[i - 1],
+            };
+        }
+
+        for module in &mut separator[..4] {
+            *module = 0;
+        }
+
+        separator
+    }
+
+    /// Generates the separator row adjacent to a row of a Stacked Omnidirectional barcode. The
+    /// separator is the complement of the row, except that it alternates next to the spaces of
+    /// the finder pattern.
+    fn omnidirectional_separator(row: &[u8], finder: Range<usize>) -> Vec<u8> {
+        let mut separator = vec![0; row.len()];
+        let mut dark = true;
+
+        for i in 4..row.len() - 4 {
+            if !finder.contains(&i) {
+                separator[i] = 1 - row[i];
+            } else if row[i] == 0 {
+                separator[i] = dark as u8;
+                dark = !dark;
+            } else {
+                dark = true;
+            }
+        }
+
+        separator
+    }
+
+    /// Returns the layout of the barcode, with the relative row heights in modules.
+    pub fn layout(&self) -> Layout {
+        let widths = self.element_widths();
+        let (left, right) = widths.split_at(LEFT_ELEMENTS);
+
+        // The rows of the stacked variants each have their own guard patterns.
+        let mut top = modules(left, false);
+        top.extend(&[1, 0]);
+        let mut bottom = vec![1, 0];
+        bottom.extend(modules(right, true));
+
+        match self.variant {
+            DataBarVariant::Omnidirectional => Layout::new(vec![Row::new(33, self.encode())]),
+            DataBarVariant::Truncated => Layout::new(vec![Row::new(13, self.encode())]),
+            DataBarVariant::Stacked => {
+                let separator = DataBar::stacked_separator(&top[..], &bottom[..]);
+
+                Layout::new(vec![Row::new(5, top), Row::new(1, separator), Row::new(7, bottom)])
+            }
+            DataBarVariant::StackedOmnidirectional => {
+                let top_finder = (GUARD.len() + 16)..(GUARD.len() + 31);
+                let bottom_finder = (GUARD.len() + 15)..(GUARD.len() + 30);
+                let top_separator = DataBar::omnidirectional_separator(&top[..], top_finder);
+                let mut bottom_separator =
+                    DataBar::omnidirectional_separator(&bottom[..], bottom_finder.clone());
+
+                // The right finder of value 3 ends in a 1 module space before its 3 module bar,
+                // so the dark separator module is moved over the start of the bar instead.
+                let (_, right) = DataBar::finders(&self.data_characters()[..]);
+                if right == 3 {
+                    bottom_separator[bottom_finder.start + 11] = 0;
+                    bottom_separator[bottom_finder.start + 12] = 1;
+                }
+                let middle = (0..top.len())
+                    .map(|i| (i > 4 && i < top.len() - 4 && i % 2 == 1) as u8)
+                    .collect();
+
+                Layout::new(vec![Row::new(33, top),
+                                 Row::new(1, top_separator),
+                                 Row::new(1, middle),
+                                 Row::new(1, bottom_separator),
+                                 Row::new(33, bottom)])
+            }
+        }
+    }
+}
+
+impl Parse for DataBar {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        13..14
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+impl Barcode for DataBar {
+    fn encode(&self) -> Vec<u8> {
+        DataBar::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        match self.variant {
+            DataBarVariant::Omnidirectional => "GS1 DataBar",
+            DataBarVariant::Truncated => "GS1 DataBar Truncated",
+            DataBarVariant::Stacked => "GS1 DataBar Stacked",
+            DataBarVariant::StackedOmnidirectional => "GS1 DataBar Stacked Omnidirectional",
+        }
+    }
+
+    fn data(&self) -> String {
+        self.digits.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum_digit().to_string())
+    }
+
+    fn layout(&self) -> Layout {
+        DataBar::layout(self)
+    }
+}
+
+/// The GS1 DataBar Limited barcode type.
+#[derive(Debug)]
+pub struct DataBarLimited(Vec<u8>);
+
+impl DataBarLimited {
+    /// Creates a new barcode.
+    /// The data can either be the 13 digits of the GTIN without the check digit, or the full 14
+    /// digits including the check digit, in which case the check digit is verified. The GTIN must
+    /// start with 0 or 1.
+    /// Returns Result<DataBarLimited, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<DataBarLimited> {
+        DataBarLimited::parse(data.as_ref()).and_then(|d| {
+            let mut digits: Vec<u8> = d.chars()
+                                       .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                                       .collect();
+            let check = if digits.len() == 14 { digits.pop() } else { None };
+            let databar = DataBarLimited(digits);
+
+            match check {
+                _ if databar.0[0] > 1 => Err(Error::Character),
+                Some(c) if c != databar.checksum_digit() => Err(Error::Checksum),
+                _ => Ok(databar),
+            }
+        })
+    }
+
+    /// Returns the GTIN-14 encoded in the barcode, including the check digit.
+    pub fn gtin(&self) -> String {
+        self.0
+            .iter()
+            .chain(Some(self.checksum_digit()).iter())
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    /// Calculates the GTIN check digit using a modulo-10 weighting algorithm.
+    fn checksum_digit(&self) -> u8 {
+        helpers::modulo_10_checksum(&self.0[..], false)
+    }
+
+    /// Calculates the check character from the modulo-89 checksum of the data characters.
+    fn check_character(characters: &[u8]) -> Vec<u8> {
+        // The weight of each element is the next power of 3, modulo 89.
+        let mut weight = 1;
+        let mut checksum = 0;
+
+        for &w in characters {
+            checksum = (checksum + weight * u32::from(w)) % 89;
+            weight = weight * 3 % 89;
+        }
+
+        let even = value_widths(checksum / 21, 8, 6, 3, true);
+        let odd = value_widths(checksum % 21, 8, 6, 3, true);
+        let mut widths: Vec<u8> = even.iter()
+                                      .zip(odd.iter())
+                                      .flat_map(|(&e, &o)| vec![e, o])
+                                      .collect();
+
+        widths.extend(&[1, 1]);
+        widths
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of encoded binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        let value = self.0.iter().fold(0u64, |acc, &d| acc * 10 + u64::from(d));
+        let (left, right) = ((value / 2_013_571) as u32, (value % 2_013_571) as u32);
+        let mut characters = character_widths(left, &LIMITED_GROUPS[..], 7, true);
+        characters.extend(character_widths(right, &LIMITED_GROUPS[..], 7, true));
+
+        let (left, right) = characters.split_at(14);
+        let mut widths = GUARD.to_vec();
+
+        widths.extend(left);
+        widths.extend(DataBarLimited::check_character(&characters[..]));
+        widths.extend(right);
+        widths.extend(&LIMITED_RIGHT_GUARD);
+        modules(&widths[..], false)
+    }
+
+    /// Returns the layout of the barcode, a single row 10 modules high.
+    pub fn layout(&self) -> Layout {
+        Layout::new(vec![Row::new(10, self.encode())])
+    }
+}
+
+impl Barcode for DataBarLimited {
+    fn encode(&self) -> Vec<u8> {
+        DataBarLimited::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "GS1 DataBar Limited"
+    }
+
+    fn data(&self) -> String {
+        self.0.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum_digit().to_string())
+    }
+
+    fn layout(&self) -> Layout {
+        DataBarLimited::layout(self)
+    }
+}
+
+impl Parse for DataBarLimited {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        13..14
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::databar::*;
+    use sym::Barcode;
+    use error::Error;
+    use std::char;
+
+    fn collapse_vec(v: Vec<u8>) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn new_databar() {
+        let databar1 = DataBar::new("0001234567890");
+        let databar2 = DataBar::new("00012345678905");
+
+        assert!(databar1.is_ok());
+        assert_eq!(databar2.unwrap().gtin(), "00012345678905");
+    }
+
+    #[test]
+    fn invalid_data_databar() {
+        assert_eq!(DataBar::new("000123456789A").err().unwrap(), Error::Character);
+        assert_eq!(DataBar::new("00012345678906").err().unwrap(), Error::Checksum);
+    }
+
+    #[test]
+    fn invalid_len_databar() {
+        assert_eq!(DataBar::new("000123456789").err().unwrap(), Error::Length);
+        assert_eq!(DataBar::new("000123456789051").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn databar_value_widths() {
+        assert_eq!(value_widths(0, 12, 4, 8, true), vec![1, 1, 2, 8]);
+        assert_eq!(value_widths(0, 4, 4, 1, false), vec![1, 1, 1, 1]);
+        assert_eq!(character_widths(0, &OUTSIDE_GROUPS[..], 4, true), vec![1, 1, 1, 1, 2, 1, 8, 1]);
+        assert_eq!(character_widths(2840, &OUTSIDE_GROUPS[..], 4, true), vec![1, 8, 1, 2, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn databar_encode() {
+        let databar1 = DataBar::new("0001234567890").unwrap();
+        let databar2 = DataBar::new("2001234567890").unwrap();
+
+        assert_eq!(collapse_vec(databar1.encode()), "010101001000000001001111111000010111001011011110111001010110000101111111000111001100111101110101");
+        assert_eq!(collapse_vec(databar2.encode()), "010100011101000001001111111000010100110110111110110000010010100101100000000111000110110110001101");
+    }
+
+    #[test]
+    fn databar_truncated_layout() {
+        let omnidirectional = DataBar::new("0001234567890").unwrap().layout();
+        let truncated = DataBar::truncated("0001234567890").unwrap().layout();
+
+        assert_eq!(omnidirectional.rows.len(), 1);
+        assert_eq!(omnidirectional.height(), 33);
+        assert_eq!(truncated.rows[0].modules, omnidirectional.rows[0].modules);
+        assert_eq!(truncated.height(), 13);
+    }
+
+    #[test]
+    fn databar_stacked_layout() {
+        let layout = DataBar::stacked("0001234567890").unwrap().layout();
+        let heights: Vec<u32> = layout.rows.iter().map(|r| r.height).collect();
+
+        assert_eq!(heights, vec![5, 1, 7]);
+        assert_eq!(collapse_vec(layout.rows[0].modules.clone()), "01010100100000000100111111100001011100101101111010");
+        assert_eq!(collapse_vec(layout.rows[1].modules.clone()), "00001010101011111010000000111010100011010010000000");
+        assert_eq!(collapse_vec(layout.rows[2].modules.clone()), "10111001010110000101111111000111001100111101110101");
+    }
+
+    #[test]
+    fn databar_stacked_omnidirectional_layout() {
+        let layout = DataBar::stacked_omnidirectional("2001234567890").unwrap().layout();
+        let heights: Vec<u32> = layout.rows.iter().map(|r| r.height).collect();
+
+        assert_eq!(heights, vec![33, 1, 1, 1, 33]);
+        assert_eq!(collapse_vec(layout.rows[0].modules.clone()), "01010001110100000100111111100001010011011011111010");
+        assert_eq!(collapse_vec(layout.rows[1].modules.clone()), "00001110001011111010000000010100101100100100000000");
+        assert_eq!(collapse_vec(layout.rows[2].modules.clone()), "00000101010101010101010101010101010101010101010000");
+        assert_eq!(collapse_vec(layout.rows[3].modules.clone()), "00001111101101011010010101010000111001001001110000");
+        assert_eq!(collapse_vec(layout.rows[4].modules.clone()), "10110000010010100101100000000111000110110110001101");
+    }
+
+    #[test]
+    fn databar_stacked_omnidirectional_finder_3() {
+        let layout = DataBar::stacked_omnidirectional("0000000000000").unwrap().layout();
+
+        assert_eq!(collapse_vec(layout.rows[4].modules[17..32].to_vec()), "101111111110111");
+        assert_eq!(collapse_vec(layout.rows[3].modules[17..32].to_vec()), "010000000000100");
+    }
+
+    #[test]
+    fn databar_as_barcode() {
+        let databar = DataBar::new("0001234567890").unwrap();
+        let stacked = DataBar::stacked("0001234567890").unwrap();
+
+        assert_eq!(databar.symbology(), "GS1 DataBar");
+        assert_eq!(databar.data(), "0001234567890");
+        assert_eq!(databar.checksum(), Some("5".to_owned()));
+        assert_eq!(databar.text(), "00012345678905");
+        assert_eq!(stacked.symbology(), "GS1 DataBar Stacked");
+        assert_eq!(Barcode::layout(&stacked), stacked.layout());
+        assert_eq!(Barcode::layout(&stacked).rows.len(), 3);
+        assert_eq!(Barcode::layout(&databar), databar.layout());
+    }
+
+    #[test]
+    fn new_databar_limited() {
+        let databar1 = DataBarLimited::new("1501234567890");
+        let databar2 = DataBarLimited::new("15012345678907");
+
+        assert!(databar1.is_ok());
+        assert_eq!(databar2.unwrap().gtin(), "15012345678907");
+    }
+
+    #[test]
+    fn invalid_data_databar_limited() {
+        assert_eq!(DataBarLimited::new("150123456789A").err().unwrap(), Error::Character);
+        assert_eq!(DataBarLimited::new("2001234567890").err().unwrap(), Error::Character);
+        assert_eq!(DataBarLimited::new("15012345678906").err().unwrap(), Error::Checksum);
+    }
+
+    #[test]
+    fn invalid_len_databar_limited() {
+        assert_eq!(DataBarLimited::new("150123456789").err().unwrap(), Error::Length);
+        assert_eq!(DataBarLimited::new("150123456789071").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn databar_limited_encode() {
+        let databar1 = DataBarLimited::new("0001234567890").unwrap();
+        let databar2 = DataBarLimited::new("1501234567890").unwrap();
+        let databar3 = DataBarLimited::new("1999999999999").unwrap();
+
+        assert_eq!(collapse_vec(databar1.encode()), "0101101011001010000010000001010101101101000101010111001100111010000010110100000");
+        assert_eq!(collapse_vec(databar2.encode()), "0100011001100011011010100111010110100101100101001001011000110111001100110100000");
+        assert_eq!(collapse_vec(databar3.encode()), "0100111100110110101101111101010101101011000101010000101110001101011110010100000");
+    }
+
+    #[test]
+    fn databar_limited_as_barcode() {
+        let databar = DataBarLimited::new("1501234567890").unwrap();
+
+        assert_eq!(databar.symbology(), "GS1 DataBar Limited");
+        assert_eq!(databar.checksum(), Some("7".to_owned()));
+        assert_eq!(databar.text(), "15012345678907");
+    }
+
+    #[test]
+    fn databar_limited_layout() {
+        let layout = DataBarLimited::new("1501234567890").unwrap().layout();
+
+        assert_eq!(layout.rows.len(), 1);
+        assert_eq!(layout.width(), 79);
+        assert_eq!(layout.height(), 10);
+    }
+}
@@ -8,9 +8,13 @@
 //!   * EAN-13
 //!   * Bookland
 //!   * JAN
+//!
+//! Bookland barcodes can also be created from an ISBN-10, ISBN-13, ISSN or ISMN, in which case
+//! the check digit of the source number is validated before it is converted.
 
-use sym::{Parse, helpers};
-use error::Result;
+use sym::{Barcode, Parse, helpers};
+use sym::ean_supp::WithAddOn;
+use error::{Error, Result};
 use std::ops::Range;
 use std::char;
 
@@ -46,13 +50,20 @@ pub const MIDDLE_GUARD: [u8; 5] = [0, 1, 0, 1, 0];
 /// The right-hand guard pattern.
 pub const RIGHT_GUARD: [u8; 3] = [1, 0, 1];
 
+/// Characters that may separate the groups of digits in an ISBN, ISSN or ISMN.
+const SEPARATORS: [char; 2] = ['-', ' '];
+
+/// The number systems used by Bookland barcodes.
+const BOOKLAND_PREFIXES: [[u8; 3]; 2] = [[9, 7, 8], [9, 7, 9]];
+
 /// The EAN-13 barcode type.
 #[derive(Debug)]
 pub struct EAN13(Vec<u8>);
 
 /// The Bookland barcode type.
-/// Bookland are EAN-13 that use number system 978.
-pub type Bookland = EAN13;
+/// Bookland are EAN-13 that use number system 978 or 979.
+#[derive(Debug)]
+pub struct Bookland(EAN13);
 
 /// The UPC-A barcode type.
 /// UPC-A are EAN-13 that start with a 0.
@@ -64,16 +75,48 @@ pub type JAN = EAN13;
 
 impl EAN13 {
     /// Creates a new barcode.
+    /// The data can either be the 12 digits without the check digit, or the full 13 digits
+    /// including the check digit, in which case the check digit is verified.
     /// Returns Result<EAN13, Error> indicating parse success.
     pub fn new<T: AsRef<str>>(data: T) -> Result<EAN13> {
         EAN13::parse(data.as_ref()).and_then(|d| {
-            let digits = d.chars()
-                          .map(|c| c.to_digit(10).expect("Unknown character") as u8)
-                          .collect();
-            Ok(EAN13(digits))
+            let mut digits: Vec<u8> = d.chars()
+                                       .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                                       .collect();
+            let check = if digits.len() == 13 { digits.pop() } else { None };
+            let ean13 = EAN13(digits);
+
+            match check {
+                Some(c) if c != ean13.checksum_digit() => Err(Error::Checksum),
+                _ => Ok(ean13),
+            }
         })
     }
 
+    /// Calculates the check digit for the given 12 digits of data.
+    /// Returns Result<u8, Error> indicating parse success.
+    pub fn check_digit<T: AsRef<str>>(data: T) -> Result<u8> {
+        match data.as_ref().len() {
+            12 => EAN13::new(data).map(|b| b.checksum_digit()),
+            _ => Err(Error::Length),
+        }
+    }
+
+    /// Validates the check digit of the given full 13 digit code.
+    /// Returns Error::Checksum if the check digit is incorrect.
+    pub fn validate<T: AsRef<str>>(data: T) -> Result<()> {
+        match data.as_ref().len() {
+            13 => EAN13::new(data).map(|_| ()),
+            _ => Err(Error::Length),
+        }
+    }
+
+    /// Creates a new barcode with an attached EAN-2 or EAN-5 add-on.
+    /// Returns Result<WithAddOn<EAN13>, Error> indicating parse success.
+    pub fn with_addon<T: AsRef<str>, U: AsRef<str>>(data: T, addon: U) -> Result<WithAddOn<EAN13>> {
+        EAN13::new(data).and_then(|b| WithAddOn::new(b, addon))
+    }
+
     /// Calculates the checksum digit using a modulo-10 weighting algorithm.
     fn checksum_digit(&self) -> u8 {
         helpers::modulo_10_checksum(&self.0[..], true)
@@ -139,6 +182,148 @@ impl EAN13 {
     }
 }
 
+impl Bookland {
+    /// Creates a new barcode.
+    /// The data can either be the 12 digits without the check digit, or the full 13 digits
+    /// including the check digit, in which case the check digit is verified. The data must start
+    /// with 978 or 979.
+    /// Returns Result<Bookland, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Bookland> {
+        let ean13 = EAN13::new(data)?;
+
+        if !Bookland::has_prefix(&ean13.0) {
+            return Err(Error::Character);
+        }
+
+        Ok(Bookland(ean13))
+    }
+
+    /// Creates a new Bookland barcode from an ISBN-10, e.g. "0-306-40615-2".
+    /// The check digit (which may be 'X') is validated and the ISBN is given the 978 prefix.
+    /// Returns Result<Bookland, Error> indicating parse success.
+    pub fn isbn10<T: AsRef<str>>(data: T) -> Result<Bookland> {
+        let isbn = Bookland::source_digits(data.as_ref(), 10, true)?;
+
+        if !Bookland::modulo_11_valid(&isbn) {
+            return Err(Error::Checksum);
+        }
+
+        Ok(Bookland(EAN13([&[9, 7, 8], &isbn[..9]].concat())))
+    }
+
+    /// Creates a new Bookland barcode from an ISBN-13, e.g. "978-0-306-40615-7".
+    /// The ISBN must start with 978 or 979 and have a valid check digit.
+    /// Returns Result<Bookland, Error> indicating parse success.
+    pub fn isbn13<T: AsRef<str>>(data: T) -> Result<Bookland> {
+        let isbn = Bookland::source_digits(data.as_ref(), 13, false)?;
+
+        if !Bookland::has_prefix(&isbn) {
+            return Err(Error::Character);
+        }
+
+        Bookland::from_digits(isbn)
+    }
+
+    /// Creates a new Bookland barcode from an ISSN, e.g. "0317-8471", and the 2-digit issue
+    /// variant (usually 0). The check digit (which may be 'X') is validated and the ISSN is
+    /// given the 977 prefix.
+    /// Returns Result<Bookland, Error> indicating parse success.
+    pub fn issn<T: AsRef<str>>(data: T, variant: u8) -> Result<Bookland> {
+        let issn = Bookland::source_digits(data.as_ref(), 8, true)?;
+
+        if !Bookland::modulo_11_valid(&issn) {
+            return Err(Error::Checksum);
+        }
+
+        if variant > 99 {
+            return Err(Error::Character);
+        }
+
+        Ok(Bookland(EAN13([&[9, 7, 7], &issn[..7], &[variant / 10, variant % 10]].concat())))
+    }
+
+    /// Creates a new Bookland barcode from an ISMN, either in the 10 character form,
+    /// e.g. "M-2306-7118-7", or the 13 digit form, e.g. "979-0-2306-7118-7".
+    /// The check digit is validated and the ISMN is given the 979-0 prefix.
+    /// Returns Result<Bookland, Error> indicating parse success.
+    pub fn ismn<T: AsRef<str>>(data: T) -> Result<Bookland> {
+        let data = data.as_ref();
+        let ismn = match data.chars().next() {
+            Some('M') | Some('m') => {
+                [&[9, 7, 9, 0], &Bookland::source_digits(&data[1..], 9, false)?[..]].concat()
+            }
+            _ => Bookland::source_digits(data, 13, false)?,
+        };
+
+        if ismn[..4] != [9, 7, 9, 0] {
+            return Err(Error::Character);
+        }
+
+        Bookland::from_digits(ismn)
+    }
+
+    /// Attaches the suggested retail price as an EAN-5 add-on, e.g. "51299".
+    /// Returns Result<WithAddOn<Bookland>, Error> indicating parse success.
+    pub fn with_price<T: AsRef<str>>(self, price: T) -> Result<WithAddOn<Bookland>> {
+        if price.as_ref().len() != 5 {
+            return Err(Error::Length);
+        }
+
+        WithAddOn::new(self, price)
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        self.0.encode()
+    }
+
+    /// Creates the barcode from all 13 digits, validating the check digit.
+    fn from_digits(mut digits: Vec<u8>) -> Result<Bookland> {
+        let check = digits.pop();
+        let ean13 = EAN13(digits);
+
+        match check {
+            Some(c) if c == ean13.checksum_digit() => Ok(Bookland(ean13)),
+            _ => Err(Error::Checksum),
+        }
+    }
+
+    /// Checks that the digits start with one of the Bookland number systems.
+    fn has_prefix(digits: &[u8]) -> bool {
+        BOOKLAND_PREFIXES.iter().any(|p| digits[..3] == p[..])
+    }
+
+    /// Converts the source number into digits, ignoring any separators. An 'X' check digit is
+    /// converted to 10 if allowed.
+    fn source_digits(data: &str, len: usize, check_x: bool) -> Result<Vec<u8>> {
+        let chars: Vec<char> = data.chars().filter(|c| !SEPARATORS.contains(c)).collect();
+
+        if chars.len() != len {
+            return Err(Error::Length);
+        }
+
+        chars.iter()
+             .enumerate()
+             .map(|(i, &c)| match c {
+                 'X' | 'x' if check_x && i == len - 1 => Ok(10),
+                 _ => c.to_digit(10).map(|d| d as u8).ok_or(Error::Character),
+             })
+             .collect()
+    }
+
+    /// Validates the modulo-11 check digit used by ISBN-10 and ISSN.
+    fn modulo_11_valid(digits: &[u8]) -> bool {
+        let sum: usize = digits.iter()
+                               .rev()
+                               .enumerate()
+                               .map(|(i, &d)| (i + 1) * d as usize)
+                               .sum();
+
+        sum % 11 == 0
+    }
+}
+
 impl Parse for EAN13 {
     /// Returns the valid length of data acceptable in this type of barcode.
     fn valid_len() -> Range<u32> {
@@ -151,9 +336,46 @@ impl Parse for EAN13 {
     }
 }
 
+impl Barcode for EAN13 {
+    fn encode(&self) -> Vec<u8> {
+        EAN13::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "EAN-13"
+    }
+
+    fn data(&self) -> String {
+        self.0.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum_digit().to_string())
+    }
+}
+
+impl Barcode for Bookland {
+    fn encode(&self) -> Vec<u8> {
+        Bookland::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        self.0.symbology()
+    }
+
+    fn data(&self) -> String {
+        self.0.data()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        self.0.checksum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ::sym::ean13::*;
+    use sym::Barcode;
     use std::char;
     use error::Error;
 
@@ -176,6 +398,13 @@ mod tests {
         assert!(bookland.is_ok());
     }
 
+    #[test]
+    fn invalid_data_bookland() {
+        let bookland = Bookland::new("750103131130");
+
+        assert_eq!(bookland.err().unwrap(), Error::Character);
+    }
+
     #[test]
     fn invalid_data_ean13() {
         let ean13 = EAN13::new("1234er123412");
@@ -210,10 +439,83 @@ mod tests {
 
     #[test]
     fn ean13_encode() {
-        let ean131 = EAN13::new("750103131130").unwrap(); // Check digit: 5
-        let ean132 = EAN13::new("983465123499").unwrap(); // Check digit: 5
+        let ean131 = EAN13::new("750103131130").unwrap(); // Check digit: 9
+        let ean132 = EAN13::new("983465123499").unwrap(); // Check digit: 3
 
         assert_eq!(collapse_vec(ean131.encode()), "10101100010100111001100101001110111101011001101010100001011001101100110100001011100101110100101");
         assert_eq!(collapse_vec(ean132.encode()), "10101101110100001001110101011110111001001100101010110110010000101011100111010011101001000010101");
     }
+
+    #[test]
+    fn ean13_as_barcode() {
+        let ean13 = EAN13::new("750103131130").unwrap();
+        let barcode: &dyn Barcode = &ean13;
+
+        assert_eq!(barcode.symbology(), "EAN-13");
+        assert_eq!(barcode.data(), "750103131130");
+        assert_eq!(barcode.checksum(), Some("9".to_owned()));
+        assert_eq!(barcode.text(), "7501031311309");
+        assert_eq!(barcode.encode(), ean13.encode());
+    }
+
+    #[test]
+    fn new_bookland_from_standard_numbers() {
+        let isbn10 = Bookland::isbn10("0-306-40615-2").unwrap();
+        let isbn10_x = Bookland::isbn10("0 8044 2957 X").unwrap();
+        let isbn13 = Bookland::isbn13("978-0-306-40615-7").unwrap();
+        let issn = Bookland::issn("0317-8471", 0).unwrap();
+        let ismn10 = Bookland::ismn("M-2306-7118-7").unwrap();
+        let ismn13 = Bookland::ismn("979-0-2306-7118-7").unwrap();
+
+        assert_eq!(isbn10.text(), "9780306406157");
+        assert_eq!(isbn10_x.text(), "9780804429573");
+        assert_eq!(isbn13.text(), "9780306406157");
+        assert_eq!(issn.text(), "9770317847001");
+        assert_eq!(ismn10.text(), "9790230671187");
+        assert_eq!(ismn13.text(), "9790230671187");
+        assert_eq!(Bookland::issn("0317-8471", 5).unwrap().text(), "9770317847056");
+    }
+
+    #[test]
+    fn invalid_bookland_from_standard_numbers() {
+        assert_eq!(Bookland::isbn10("0-306-40615-3").err().unwrap(), Error::Checksum);
+        assert_eq!(Bookland::isbn10("0-306-40615").err().unwrap(), Error::Length);
+        assert_eq!(Bookland::isbn10("0-306-4061X-2").err().unwrap(), Error::Character);
+        assert_eq!(Bookland::isbn13("978-0-306-40615-8").err().unwrap(), Error::Checksum);
+        assert_eq!(Bookland::isbn13("977-0-306-40615-7").err().unwrap(), Error::Character);
+        assert_eq!(Bookland::issn("0317-8472", 0).err().unwrap(), Error::Checksum);
+        assert_eq!(Bookland::issn("0317-8471", 100).err().unwrap(), Error::Character);
+        assert_eq!(Bookland::ismn("M-2306-7118-8").err().unwrap(), Error::Checksum);
+        assert_eq!(Bookland::ismn("978-0-306-40615-7").err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn bookland_with_price() {
+        let bookland = Bookland::isbn10("0-306-40615-2").unwrap().with_price("51299").unwrap();
+
+        assert_eq!(bookland.text(), "9780306406157 51299");
+        assert_eq!(bookland.addon().symbology(), "EAN-5");
+        assert_eq!(Bookland::isbn10("0-306-40615-2").unwrap().with_price("12").err().unwrap(),
+                   Error::Length);
+    }
+
+    #[test]
+    fn ean13_with_check_digit() {
+        let ean13 = EAN13::new("7501031311309").unwrap();
+        let upca = UPCA::new("0123456123458").unwrap();
+
+        assert_eq!(ean13.data(), "750103131130");
+        assert_eq!(collapse_vec(ean13.encode()), collapse_vec(EAN13::new("750103131130").unwrap().encode()));
+        assert_eq!(upca.text(), "0123456123458");
+        assert_eq!(EAN13::new("7501031311305").err().unwrap(), Error::Checksum);
+    }
+
+    #[test]
+    fn ean13_check_digit() {
+        assert_eq!(EAN13::check_digit("750103131130"), Ok(9));
+        assert_eq!(EAN13::check_digit("7501031311309").err().unwrap(), Error::Length);
+        assert_eq!(EAN13::validate("7501031311309"), Ok(()));
+        assert_eq!(EAN13::validate("7501031311305").err().unwrap(), Error::Checksum);
+        assert_eq!(EAN13::validate("750103131130").err().unwrap(), Error::Length);
+    }
 }
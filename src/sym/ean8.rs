@@ -3,8 +3,9 @@
 //! EAN-8 barcodes are EAN style barcodes for smaller packages on products like
 //! cigaretts, chewing gum, etc where package space is limited.
 
-use sym::{Parse, helpers};
-use error::Result;
+use sym::{Barcode, Parse, helpers};
+use sym::ean_supp::WithAddOn;
+use error::{Error, Result};
 use sym::ean13::{ENCODINGS,
                  LEFT_GUARD,
                  MIDDLE_GUARD,
@@ -18,16 +19,48 @@ pub struct EAN8(Vec<u8>);
 
 impl EAN8 {
     /// Creates a new barcode.
-    /// Returns Result<EAN8, String> indicating parse success.
+    /// The data can either be the 7 digits without the check digit, or the full 8 digits
+    /// including the check digit, in which case the check digit is verified.
+    /// Returns Result<EAN8, Error> indicating parse success.
     pub fn new<T: AsRef<str>>(data: T) -> Result<EAN8> {
         EAN8::parse(data.as_ref()).and_then(|d| {
-            let digits = d.chars()
-                          .map(|c| c.to_digit(10).expect("Unknown character") as u8)
-                          .collect();
-            Ok(EAN8(digits))
+            let mut digits: Vec<u8> = d.chars()
+                                       .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                                       .collect();
+            let check = if digits.len() == 8 { digits.pop() } else { None };
+            let ean8 = EAN8(digits);
+
+            match check {
+                Some(c) if c != ean8.checksum_digit() => Err(Error::Checksum),
+                _ => Ok(ean8),
+            }
         })
     }
 
+    /// Calculates the check digit for the given 7 digits of data.
+    /// Returns Result<u8, Error> indicating parse success.
+    pub fn check_digit<T: AsRef<str>>(data: T) -> Result<u8> {
+        match data.as_ref().len() {
+            7 => EAN8::new(data).map(|b| b.checksum_digit()),
+            _ => Err(Error::Length),
+        }
+    }
+
+    /// Validates the check digit of the given full 8 digit code.
+    /// Returns Error::Checksum if the check digit is incorrect.
+    pub fn validate<T: AsRef<str>>(data: T) -> Result<()> {
+        match data.as_ref().len() {
+            8 => EAN8::new(data).map(|_| ()),
+            _ => Err(Error::Length),
+        }
+    }
+
+    /// Creates a new barcode with an attached EAN-2 or EAN-5 add-on.
+    /// Returns Result<WithAddOn<EAN8>, Error> indicating parse success.
+    pub fn with_addon<T: AsRef<str>, U: AsRef<str>>(data: T, addon: U) -> Result<WithAddOn<EAN8>> {
+        EAN8::new(data).and_then(|b| WithAddOn::new(b, addon))
+    }
+
     /// Calculates the checksum digit using a weighting algorithm.
     fn checksum_digit(&self) -> u8 {
         helpers::modulo_10_checksum(&self.0[..], false)
@@ -106,9 +139,28 @@ impl Parse for EAN8 {
     }
 }
 
+impl Barcode for EAN8 {
+    fn encode(&self) -> Vec<u8> {
+        EAN8::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "EAN-8"
+    }
+
+    fn data(&self) -> String {
+        self.0.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum_digit().to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::ean8::*;
+    use sym::Barcode;
     use error::Error;
     use std::char;
 
@@ -146,4 +198,31 @@ mod tests {
         assert_eq!(collapse_vec(ean81.encode()), "1010110001011000100110010010011010101000010101110010011101000100101");
         assert_eq!(collapse_vec(ean82.encode()), "1010001011011011101111010100011010101010000100111011001101010000101");
     }
+
+    #[test]
+    fn ean8_as_barcode() {
+        let ean8 = EAN8::new("5512345").unwrap();
+
+        assert_eq!(ean8.symbology(), "EAN-8");
+        assert_eq!(ean8.checksum(), Some("7".to_owned()));
+        assert_eq!(ean8.text(), "55123457");
+    }
+
+    #[test]
+    fn ean8_with_check_digit() {
+        let ean8 = EAN8::new("55123457").unwrap();
+
+        assert_eq!(ean8.data(), "5512345");
+        assert_eq!(collapse_vec(ean8.encode()), collapse_vec(EAN8::new("5512345").unwrap().encode()));
+        assert_eq!(EAN8::new("55123458").err().unwrap(), Error::Checksum);
+    }
+
+    #[test]
+    fn ean8_check_digit() {
+        assert_eq!(EAN8::check_digit("5512345"), Ok(7));
+        assert_eq!(EAN8::check_digit("55123457").err().unwrap(), Error::Length);
+        assert_eq!(EAN8::validate("55123457"), Ok(()));
+        assert_eq!(EAN8::validate("55123458").err().unwrap(), Error::Checksum);
+        assert_eq!(EAN8::validate("5512345").err().unwrap(), Error::Length);
+    }
 }
@@ -5,11 +5,15 @@
 //! EAN-5 barcodes are often used to indicate the suggested retail price of books.
 //!
 //! These supplemental barcodes never appear without a full EAN-13 barcode alongside them.
+//!
+//! The `WithAddOn` type attaches a supplemental to an EAN-13, UPC-A, EAN-8 or UPC-E barcode as
+//! a single symbol, separated by a quiet zone of 7 to 12 modules. These are usually created with
+//! the `with_addon` constructor of the main barcode type, e.g. `EAN13::with_addon`.
 
-use sym::{Parse, helpers};
+use sym::{Barcode, Parse, helpers};
 use error::{Error, Result};
 use sym::ean13::ENCODINGS;
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 use std::char;
 
 const LEFT_GUARD: [u8; 4] = [1, 0, 1, 1];
@@ -28,6 +32,12 @@ const EAN2_PARITY: [[usize; 5]; 4] = [
     [1,1,0,0,0],
 ];
 
+/// The valid range of the gap (in modules) between a barcode and its add-on.
+const ADDON_GAP: RangeInclusive<usize> = 7..=12;
+
+/// The default gap (in modules) between a barcode and its add-on.
+const DEFAULT_ADDON_GAP: usize = 9;
+
 /// The Supplemental EAN barcode type.
 #[derive(Debug)]
 pub enum EANSUPP {
@@ -139,9 +149,116 @@ impl Parse for EANSUPP {
     }
 }
 
+impl Barcode for EANSUPP {
+    fn encode(&self) -> Vec<u8> {
+        EANSUPP::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        match *self {
+            EANSUPP::EAN2(_) => "EAN-2",
+            EANSUPP::EAN5(_) => "EAN-5",
+        }
+    }
+
+    fn data(&self) -> String {
+        self.raw_data().iter().map(|d| d.to_string()).collect()
+    }
+
+    // The EAN-5 check digit is implied by the parity pattern rather than being encoded.
+    fn checksum(&self) -> Option<String> {
+        match *self {
+            EANSUPP::EAN2(_) => None,
+            EANSUPP::EAN5(_) => Some(self.checksum_digit().to_string()),
+        }
+    }
+
+    fn text(&self) -> String {
+        self.data()
+    }
+}
+
+/// An EAN/UPC barcode with an attached EAN-2 or EAN-5 add-on, encoded as a single symbol.
+#[derive(Debug)]
+pub struct WithAddOn<T> {
+    main: T,
+    addon: EANSUPP,
+    gap: usize,
+}
+
+impl<T: Barcode> WithAddOn<T> {
+    /// Creates a new barcode from the main barcode and add-on data, using the default gap of 9
+    /// modules between them.
+    /// Returns Result<WithAddOn, Error> indicating parse success of the add-on.
+    pub fn new<U: AsRef<str>>(main: T, addon: U) -> Result<WithAddOn<T>> {
+        EANSUPP::new(addon).map(|addon| WithAddOn { main, addon, gap: DEFAULT_ADDON_GAP })
+    }
+
+    /// Sets the gap (in modules) between the main barcode and the add-on.
+    /// Returns Error::Gap if the gap is not between 7 and 12 modules, leaving the gap unchanged.
+    pub fn set_gap(&mut self, modules: usize) -> Result<()> {
+        if !ADDON_GAP.contains(&modules) {
+            return Err(Error::Gap);
+        }
+
+        self.gap = modules;
+        Ok(())
+    }
+
+    /// Returns the main barcode.
+    pub fn main(&self) -> &T {
+        &self.main
+    }
+
+    /// Returns the add-on barcode.
+    pub fn addon(&self) -> &EANSUPP {
+        &self.addon
+    }
+
+    /// Returns the index of the first module of the add-on in the encoded barcode.
+    /// Generators can use this to render the add-on with shorter bars and its text above.
+    pub fn addon_start(&self) -> usize {
+        self.main.encode().len() + self.gap
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        helpers::join_slices(&[&self.main.encode()[..],
+                               &vec![0; self.gap][..],
+                               &self.addon.encode()[..]][..])
+    }
+}
+
+impl<T: Barcode> Barcode for WithAddOn<T> {
+    fn encode(&self) -> Vec<u8> {
+        WithAddOn::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        self.main.symbology()
+    }
+
+    fn data(&self) -> String {
+        self.main.data()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        self.main.checksum()
+    }
+
+    fn text(&self) -> String {
+        format!("{} {}", self.main.text(), self.addon.text())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::ean_supp::*;
+    use sym::ean13::{EAN13, UPCA};
+    use sym::ean8::EAN8;
+    use sym::upce::UPCE;
+    use sym::Barcode;
     use error::Error;
     use std::char;
 
@@ -194,4 +311,75 @@ mod tests {
                    "10110110001010011001010011011010111101010011101");
     }
 
+    #[test]
+    fn ean_supp_as_barcode() {
+        let ean2 = EANSUPP::new("34").unwrap();
+        let ean5 = EANSUPP::new("51234").unwrap();
+
+        assert_eq!(ean2.symbology(), "EAN-2");
+        assert_eq!(ean2.checksum(), None);
+        assert_eq!(ean5.symbology(), "EAN-5");
+        assert_eq!(ean5.checksum(), Some("9".to_owned()));
+        assert_eq!(ean5.text(), "51234");
+    }
+
+    #[test]
+    fn new_with_addon() {
+        let ean13 = EAN13::with_addon("978012345678", "51299");
+        let upca = UPCA::with_addon("012345612345", "12");
+        let ean8 = EAN8::with_addon("5512345", "34");
+        let upce = UPCE::with_addon("0425261", "51234");
+
+        assert!(ean13.is_ok());
+        assert!(upca.is_ok());
+        assert!(ean8.is_ok());
+        assert!(upce.is_ok());
+    }
+
+    #[test]
+    fn invalid_with_addon() {
+        let ean13_1 = EAN13::with_addon("978012345678", "123");
+        let ean13_2 = EAN13::with_addon("978012345678", "1A");
+        let ean13_3 = EAN13::with_addon("97801234567A", "12");
+
+        assert_eq!(ean13_1.err().unwrap(), Error::Length);
+        assert_eq!(ean13_2.err().unwrap(), Error::Character);
+        assert_eq!(ean13_3.err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn with_addon_gap() {
+        let mut ean8 = EAN8::with_addon("5512345", "34").unwrap();
+
+        assert_eq!(ean8.addon_start(), 76);
+        assert_eq!(ean8.set_gap(12), Ok(()));
+        assert_eq!(ean8.addon_start(), 79);
+        assert_eq!(ean8.set_gap(6).err().unwrap(), Error::Gap);
+        assert_eq!(ean8.set_gap(13).err().unwrap(), Error::Gap);
+        assert_eq!(ean8.addon_start(), 79);
+    }
+
+    #[test]
+    fn with_addon_encode() {
+        let mut ean8 = EAN8::with_addon("5512345", "34").unwrap();
+        ean8.set_gap(7).unwrap();
+
+        assert_eq!(collapse_vec(ean8.encode()),
+                   "1010110001011000100110010010011010101000010101110010011101000100101\
+                    0000000\
+                    10110100001010100011");
+        assert_eq!(ean8.addon_start(), 74);
+        assert_eq!(ean8.encode()[74..], EANSUPP::new("34").unwrap().encode()[..]);
+    }
+
+    #[test]
+    fn with_addon_as_barcode() {
+        let upce = UPCE::with_addon("0425261", "51234").unwrap();
+
+        assert_eq!(upce.symbology(), "UPC-E");
+        assert_eq!(upce.checksum(), Some("4".to_owned()));
+        assert_eq!(upce.text(), "04252614 51234");
+        assert_eq!(upce.addon().symbology(), "EAN-5");
+        assert_eq!(upce.main().data(), "0425261");
+    }
 }
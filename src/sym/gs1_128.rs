@@ -0,0 +1,342 @@
+//! Encoder for GS1-128 barcodes.
+//!
+//! GS1-128 (formerly UCC/EAN-128) is a Code128 barcode that carries one or more GS1 element
+//! strings. Each element string is an Application Identifier (AI) followed by its data, and is
+//! used widely in logistics for shipping labels, batch numbers, expiry dates, etc.
+//!
+//! Data is provided in the human-readable syntax, with each AI in parentheses:
+//!
+//! <ul><li>(01)09501101530003(17)250101(10)ABC123</li></ul>
+//!
+//! Each element string is validated against the GS1 AI table. An FNC1 character is inserted at
+//! the start of the barcode and after each variable-length element string (except the last).
+//!
+//! NOTE: As parentheses delimit the AIs, they cannot appear within the data itself.
+
+use sym::{Barcode, helpers};
+use sym::code128::Code128;
+use error::{Error, GS1Error, Result};
+use std::fmt;
+
+// Data types for the AI table: N = numeric, X = alphanumeric (GS1 character set 82).
+const N: bool = true;
+const X: bool = false;
+
+// Application Identifier -> (numeric, minimum length, maximum length, check digit position)
+// mappings. A trailing 'n' in an AI matches any digit (such as a decimal point position). The
+// check digit position is the number of leading digits ending in a GS1 mod-10 check digit (0 = no
+// check digit).
+const AIS: [(&str, bool, usize, usize, usize); 125] = [
+    ("00", N, 18, 18, 18), ("01", N, 14, 14, 14), ("02", N, 14, 14, 14), ("03", N, 14, 14, 14),
+    ("10", X, 1, 20, 0), ("11", N, 6, 6, 0), ("12", N, 6, 6, 0), ("13", N, 6, 6, 0),
+    ("15", N, 6, 6, 0), ("16", N, 6, 6, 0), ("17", N, 6, 6, 0), ("20", N, 2, 2, 0),
+    ("21", X, 1, 20, 0), ("22", X, 1, 20, 0), ("235", X, 1, 28, 0), ("240", X, 1, 30, 0),
+    ("241", X, 1, 30, 0), ("242", N, 1, 6, 0), ("243", X, 1, 20, 0), ("250", X, 1, 30, 0),
+    ("251", X, 1, 30, 0), ("253", X, 13, 30, 13), ("254", X, 1, 20, 0), ("255", N, 13, 25, 13),
+    ("30", N, 1, 8, 0), ("310n", N, 6, 6, 0), ("311n", N, 6, 6, 0), ("312n", N, 6, 6, 0),
+    ("313n", N, 6, 6, 0), ("314n", N, 6, 6, 0), ("315n", N, 6, 6, 0), ("316n", N, 6, 6, 0),
+    ("320n", N, 6, 6, 0), ("321n", N, 6, 6, 0), ("322n", N, 6, 6, 0), ("323n", N, 6, 6, 0),
+    ("324n", N, 6, 6, 0), ("325n", N, 6, 6, 0), ("326n", N, 6, 6, 0), ("327n", N, 6, 6, 0),
+    ("328n", N, 6, 6, 0), ("329n", N, 6, 6, 0), ("330n", N, 6, 6, 0), ("331n", N, 6, 6, 0),
+    ("332n", N, 6, 6, 0), ("333n", N, 6, 6, 0), ("334n", N, 6, 6, 0), ("335n", N, 6, 6, 0),
+    ("336n", N, 6, 6, 0), ("337n", N, 6, 6, 0), ("340n", N, 6, 6, 0), ("341n", N, 6, 6, 0),
+    ("342n", N, 6, 6, 0), ("343n", N, 6, 6, 0), ("344n", N, 6, 6, 0), ("345n", N, 6, 6, 0),
+    ("346n", N, 6, 6, 0), ("347n", N, 6, 6, 0), ("348n", N, 6, 6, 0), ("349n", N, 6, 6, 0),
+    ("350n", N, 6, 6, 0), ("351n", N, 6, 6, 0), ("352n", N, 6, 6, 0), ("353n", N, 6, 6, 0),
+    ("354n", N, 6, 6, 0), ("355n", N, 6, 6, 0), ("356n", N, 6, 6, 0), ("357n", N, 6, 6, 0),
+    ("360n", N, 6, 6, 0), ("361n", N, 6, 6, 0), ("362n", N, 6, 6, 0), ("363n", N, 6, 6, 0),
+    ("364n", N, 6, 6, 0), ("365n", N, 6, 6, 0), ("366n", N, 6, 6, 0), ("367n", N, 6, 6, 0),
+    ("368n", N, 6, 6, 0), ("369n", N, 6, 6, 0), ("37", N, 1, 8, 0), ("390n", N, 1, 15, 0),
+    ("391n", N, 4, 18, 0), ("392n", N, 1, 15, 0), ("393n", N, 4, 18, 0), ("394n", N, 4, 4, 0),
+    ("400", X, 1, 30, 0), ("401", X, 1, 30, 0), ("402", N, 17, 17, 17), ("403", X, 1, 30, 0),
+    ("410", N, 13, 13, 13), ("411", N, 13, 13, 13), ("412", N, 13, 13, 13),
+    ("413", N, 13, 13, 13), ("414", N, 13, 13, 13), ("415", N, 13, 13, 13),
+    ("416", N, 13, 13, 13), ("417", N, 13, 13, 13), ("420", X, 1, 20, 0), ("421", X, 4, 12, 0),
+    ("422", N, 3, 3, 0), ("423", N, 3, 15, 0), ("424", N, 3, 3, 0), ("425", N, 3, 15, 0),
+    ("426", N, 3, 3, 0),
+    ("7001", N, 13, 13, 0), ("7002", X, 1, 30, 0), ("7003", N, 10, 10, 0), ("7004", N, 1, 4, 0),
+    ("8001", N, 14, 14, 0), ("8002", X, 1, 20, 0), ("8003", X, 14, 30, 14), ("8004", X, 1, 30, 0),
+    ("8005", N, 6, 6, 0), ("8006", N, 18, 18, 14), ("8007", X, 1, 34, 0), ("8008", N, 8, 12, 0),
+    ("8010", X, 1, 30, 0), ("8011", N, 1, 12, 0), ("8012", X, 1, 20, 0), ("8017", N, 18, 18, 18),
+    ("8018", N, 18, 18, 18), ("8019", N, 1, 10, 0), ("8020", X, 1, 25, 0), ("8200", X, 1, 70, 0),
+    ("90", X, 1, 30, 0), ("9n", X, 1, 90, 0),
+];
+
+// AI prefixes with a predefined element string length. These never require an FNC1 separator.
+const PREDEFINED: [&str; 22] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16",
+    "17", "18", "19", "20", "31", "32", "33", "34", "35", "36", "41",
+];
+
+// The characters (other than digits and letters) allowed in alphanumeric GS1 data. Parentheses
+// are also in the GS1 character set, but are left out as they delimit the AIs.
+const SPECIAL_CHARS: &str = "!\"%&'*+,-./:;<=>?_";
+
+// The maximum number of data characters (including AIs) in a GS1-128 barcode.
+const MAX_LEN: usize = 48;
+
+/// A GS1 Application Identifier, such as (01) or (8003).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AI {
+    digits: [u8; 4],
+    len: usize,
+}
+
+impl AI {
+    fn new(s: &str) -> Option<AI> {
+        if s.len() < 2 || s.len() > 4 || !s.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut digits = [0; 4];
+
+        for (i, b) in s.bytes().enumerate() {
+            digits[i] = b - b'0';
+        }
+
+        Some(AI { digits, len: s.len() })
+    }
+
+    fn matches(&self, pattern: &str) -> bool {
+        pattern.len() == self.len &&
+            pattern.chars()
+                   .zip(self.digits.iter())
+                   .all(|(p, &d)| p == 'n' || p.to_digit(10) == Some(d as u32))
+    }
+
+    fn format(&self) -> Option<(bool, usize, usize, usize)> {
+        AIS.iter()
+           .find(|f| self.matches(f.0))
+           .map(|&(_, numeric, min, max, check)| (numeric, min, max, check))
+    }
+
+    fn is_predefined(&self) -> bool {
+        PREDEFINED.iter().any(|p| self.code().starts_with(p))
+    }
+
+    /// Returns the digits of the Application Identifier.
+    pub fn code(&self) -> String {
+        self.digits[..self.len].iter().map(|d| d.to_string()).collect()
+    }
+}
+
+impl fmt::Display for AI {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({})", self.code())
+    }
+}
+
+/// The GS1-128 barcode type.
+#[derive(Debug)]
+pub struct GS1128 {
+    elements: Vec<(AI, String)>,
+    code128: Code128,
+}
+
+/// The UCC/EAN-128 barcode type.
+pub type EAN128 = GS1128;
+
+impl GS1128 {
+    /// Creates a new barcode.
+    /// Returns Result<GS1128, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<GS1128> {
+        let elements = GS1128::parse(data.as_ref())?;
+        let mut fnc1 = true;
+        let mut text = String::new();
+
+        for &(ai, ref value) in &elements {
+            if fnc1 {
+                text.push('\u{0179}');
+            }
+
+            text.push_str(&ai.code());
+            text.push_str(value);
+            fnc1 = !ai.is_predefined();
+        }
+
+        if text.chars().filter(|&c| c != '\u{0179}').count() > MAX_LEN {
+            return Err(Error::Length);
+        }
+
+        Code128::auto(text).map(|code128| GS1128 { elements, code128 })
+    }
+
+    /// Returns the parsed element strings as (AI, data) pairs.
+    pub fn elements(&self) -> &[(AI, String)] {
+        &self.elements[..]
+    }
+
+    // Splits the data into element strings and validates each against the AI table.
+    fn parse(data: &str) -> Result<Vec<(AI, String)>> {
+        if data.is_empty() {
+            return Err(Error::Length);
+        }
+
+        if !data.starts_with('(') {
+            return Err(Error::Character);
+        }
+
+        data[1..].split('(')
+                 .map(|element| {
+                     let mut parts = element.splitn(2, ')');
+                     let ai = parts.next().and_then(AI::new).ok_or(Error::Character)?;
+                     let value = parts.next().ok_or(Error::Character)?;
+
+                     GS1128::validate(ai, value).map(|_| (ai, value.to_owned()))
+                 })
+                 .collect()
+    }
+
+    fn validate(ai: AI, value: &str) -> Result<()> {
+        let invalid = |e| Err(Error::GS1(ai.code(), e));
+        let (numeric, min, max, check) = match ai.format() {
+            Some(f) => f,
+            None => return invalid(GS1Error::Unknown),
+        };
+        let valid_char = |c: char| {
+            c.is_ascii_digit() || (!numeric && (c.is_ascii_alphabetic() || SPECIAL_CHARS.contains(c)))
+        };
+
+        if value.len() < min || value.len() > max {
+            return invalid(GS1Error::Length);
+        }
+
+        if !value.chars().all(valid_char) {
+            return invalid(GS1Error::Character);
+        }
+
+        if check > 0 {
+            let digits: Vec<u8> = value[..check].chars()
+                                                .filter_map(|c| c.to_digit(10))
+                                                .map(|d| d as u8)
+                                                .collect();
+
+            if digits.len() < check {
+                return invalid(GS1Error::Character);
+            }
+
+            let (data, check_digit) = digits.split_at(check - 1);
+
+            if helpers::modulo_10_checksum(data, data.len() % 2 == 0) != check_digit[0] {
+                return invalid(GS1Error::CheckDigit);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        self.code128.encode()
+    }
+}
+
+impl Barcode for GS1128 {
+    fn encode(&self) -> Vec<u8> {
+        GS1128::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "GS1-128"
+    }
+
+    fn data(&self) -> String {
+        self.elements
+            .iter()
+            .map(|&(ai, ref value)| format!("{}{}", ai, value))
+            .collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        self.code128.checksum()
+    }
+
+    fn text(&self) -> String {
+        self.data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::gs1_128::*;
+    use sym::Barcode;
+    use error::{Error, GS1Error};
+
+    #[test]
+    fn new_gs1_128() {
+        let gs1_128 = GS1128::new("(01)09501101530003(17)250101(10)ABC123").unwrap();
+        let elements = gs1_128.elements();
+
+        assert_eq!(elements.len(), 3);
+        assert_eq!(elements[0].0.to_string(), "(01)");
+        assert_eq!(elements[0].0.code(), "01");
+        assert_eq!(elements[0].1, "09501101530003");
+        assert_eq!(elements[2].0.to_string(), "(10)");
+        assert_eq!(gs1_128.text(), "(01)09501101530003(17)250101(10)ABC123");
+    }
+
+    #[test]
+    fn invalid_data_gs1_128() {
+        let gs1_128_a = GS1128::new("0109501101530003");
+        let gs1_128_b = GS1128::new("(01)09501101530004");
+        let gs1_128_c = GS1128::new("(01)0950110153000");
+        let gs1_128_d = GS1128::new("(17)25010A");
+        let gs1_128_e = GS1128::new("(01)09501101530003(10)ABC{");
+        let gs1_128_f = GS1128::new("(89)1234");
+        let gs1_128_g = GS1128::new("(10)AB)C");
+        let gs1_128_h = GS1128::new("(418)5412345000013");
+
+        assert_eq!(gs1_128_a.err().unwrap(), Error::Character);
+        assert_eq!(gs1_128_b.err().unwrap().to_string(),
+                   "GS1 element string (01) is invalid: check digit is incorrect");
+        assert_eq!(gs1_128_c.err().unwrap(), Error::GS1("01".to_owned(), GS1Error::Length));
+        assert_eq!(gs1_128_d.err().unwrap(), Error::GS1("17".to_owned(), GS1Error::Character));
+        assert_eq!(gs1_128_e.err().unwrap(), Error::GS1("10".to_owned(), GS1Error::Character));
+        assert_eq!(gs1_128_f.err().unwrap(), Error::GS1("89".to_owned(), GS1Error::Unknown));
+        assert_eq!(gs1_128_g.err().unwrap(), Error::GS1("10".to_owned(), GS1Error::Character));
+        assert_eq!(gs1_128_h.err().unwrap(), Error::GS1("418".to_owned(), GS1Error::Unknown));
+    }
+
+    #[test]
+    fn invalid_length_gs1_128() {
+        let gs1_128_a = GS1128::new("");
+        let gs1_128_b = GS1128::new("(400)ABCDEFGHIJKLMNOPQRSTUVWXYZ1234(401)ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+
+        assert_eq!(gs1_128_a.err().unwrap(), Error::Length);
+        assert_eq!(gs1_128_b.err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn gs1_128_decimal_ais() {
+        let gs1_128 = GS1128::new("(3103)000189(3922)1234");
+
+        assert!(gs1_128.is_ok());
+    }
+
+    #[test]
+    fn gs1_128_gln_ais() {
+        assert!(GS1128::new("(410)5412345000013").is_ok());
+        assert!(GS1128::new("(417)5412345000013").is_ok());
+    }
+
+    #[test]
+    fn gs1_128_sscc() {
+        let gs1_128 = GS1128::new("(00)999999999999999995");
+
+        assert!(gs1_128.is_ok());
+    }
+
+    #[test]
+    fn gs1_128_encode() {
+        let gs1_128_a = GS1128::new("(01)09501101530003(17)250101(10)ABC123").unwrap();
+        let gs1_128_b = GS1128::new("(10)ABC123(01)09501101530003").unwrap();
+
+        assert_eq!(gs1_128_a.encode(),
+                   Code128::auto("\u{0179}01095011015300031725010110ABC123").unwrap().encode());
+        assert_eq!(gs1_128_b.encode(),
+                   Code128::auto("\u{0179}10ABC123\u{0179}0109501101530003").unwrap().encode());
+        assert_eq!(gs1_128_b.encode(),
+                   Code128::new("ƁŹ10ABC1Ć23Ź0109501101530003").unwrap().encode());
+    }
+}
@@ -1,3 +1,6 @@
+use sym::Ratio;
+use std::iter::repeat;
+
 /// Joins and flattens the given slice of &[u8] slices into a Vec<u8>.
 /// TODO: Work out how to use join_iters with slices and then remove this function.
 pub fn join_slices(slices: &[&[u8]]) -> Vec<u8> {
@@ -17,13 +20,13 @@ pub fn join_iters<'a, T: Iterator>(iters: T) -> Vec<u8>
 
 /// Calculates the checksum digit using a modulo-10 weighting algorithm.
 pub fn modulo_10_checksum(data: &[u8], even_start: bool) -> u8 {
-    let mut odds = 0;
-    let mut evens = 0;
+    let mut odds = 0u32;
+    let mut evens = 0u32;
 
     for (i, d) in data.iter().enumerate() {
         match i % 2 {
-            1 => odds += *d,
-            _ => evens += *d,
+            1 => odds += u32::from(*d),
+            _ => evens += u32::from(*d),
         }
     }
 
@@ -37,6 +40,24 @@ pub fn modulo_10_checksum(data: &[u8], even_start: bool) -> u8 {
 
     match 10 - ((odds + evens) % 10) {
         10 => 0,
-        n => n,
+        n => n as u8,
     }
 }
+
+/// Re-encodes the modules of a two-width barcode using the given wide-to-narrow ratio.
+/// Runs of a single module are narrow elements and longer runs are wide elements.
+pub fn apply_ratio(modules: &[u8], ratio: Ratio) -> Vec<u8> {
+    let (narrow, wide) = ratio.widths();
+    let mut encoding = vec![];
+    let mut i = 0;
+
+    while i < modules.len() {
+        let module = modules[i];
+        let run = modules[i..].iter().take_while(|&&m| m == module).count();
+
+        encoding.extend(repeat(module).take(if run == 1 { narrow } else { wide }));
+        i += run;
+    }
+
+    encoding
+}
@@ -0,0 +1,270 @@
+//! Encoder for USPS Intelligent Mail barcodes (IMb).
+//!
+//! The Intelligent Mail barcode is a 4-state symbology used by the United States Postal Service.
+//! It encodes a 20 digit tracking code, optionally followed by a 5, 9 or 11 digit routing code
+//! (the delivery point ZIP code), in 65 bars.
+//!
+//! As each bar has one of four states rather than being on or off, barcodes are encoded as a
+//! sequence of `State`s, which the generators can render via the barcode's `Layout`.
+
+use sym::Parse;
+use sym::layout::{Layout, State};
+use error::{Error, Result};
+use std::ops::Range;
+use std::char;
+
+// The valid lengths of the routing code.
+const ROUTING_LENGTHS: [usize; 4] = [0, 5, 9, 11];
+
+// The CRC-11 generator polynomial used for the frame check sequence.
+const FCS_POLYNOMIAL: u16 = 0x0F35;
+
+// Bar -> character bit mappings. Each entry is the (1-based) bar of the corresponding bit of the
+// 10 characters, where bars 1-65 are descenders and bars 66-130 are ascenders.
+const BARS: [u8; 130] = [
+    67, 6, 78, 16, 86, 95, 34, 40, 45, 113, 117, 121, 62,
+    87, 18, 104, 41, 76, 57, 119, 115, 72, 97, 2, 127, 26,
+    105, 35, 122, 52, 114, 7, 24, 82, 68, 63, 94, 44, 77,
+    112, 70, 100, 39, 30, 107, 15, 125, 85, 10, 65, 54, 88,
+    20, 106, 46, 66, 8, 116, 29, 61, 99, 80, 90, 37, 123,
+    51, 25, 84, 129, 56, 4, 109, 96, 28, 36, 47, 11, 71,
+    33, 102, 21, 9, 17, 49, 124, 79, 64, 91, 42, 69, 53,
+    60, 14, 1, 27, 103, 126, 75, 89, 50, 120, 19, 32, 110,
+    92, 111, 130, 59, 31, 12, 81, 43, 55, 5, 74, 22, 101,
+    128, 58, 118, 48, 108, 38, 98, 93, 23, 83, 13, 73, 3,
+];
+
+/// The USPS Intelligent Mail barcode type.
+#[derive(Debug)]
+pub struct IMB(Vec<u8>);
+
+impl IMB {
+    /// Creates a new barcode.
+    /// The data is the 20 digit tracking code, followed by the 0, 5, 9 or 11 digit routing code.
+    /// The second digit of the tracking code must be between 0 and 4.
+    /// Returns Result<IMB, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<IMB> {
+        let d = IMB::parse(data.as_ref())?;
+
+        if !ROUTING_LENGTHS.contains(&(d.len() - 20)) {
+            return Err(Error::Length);
+        }
+
+        let digits: Vec<u8> = d.chars()
+                               .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                               .collect();
+
+        match digits[1] {
+            0..=4 => Ok(IMB(digits)),
+            _ => Err(Error::Character),
+        }
+    }
+
+    /// Returns the 20 digit tracking code.
+    pub fn tracking_code(&self) -> String {
+        self.0[..20].iter().map(|d| d.to_string()).collect()
+    }
+
+    /// Returns the routing code, which may be empty.
+    pub fn routing_code(&self) -> String {
+        self.0[20..].iter().map(|d| d.to_string()).collect()
+    }
+
+    /// Converts the tracking and routing codes into a single binary value.
+    fn binary(&self) -> u128 {
+        let routing = self.0[20..].iter().fold(0, |acc, &d| acc * 10 + u128::from(d));
+        let mut value = match self.0.len() - 20 {
+            0 => 0,
+            5 => routing + 1,
+            9 => routing + 100_001,
+            _ => routing + 1_000_100_001,
+        };
+
+        for (i, &d) in self.0[..20].iter().enumerate() {
+            value = value * if i == 1 { 5 } else { 10 } + u128::from(d);
+        }
+
+        value
+    }
+
+    /// Calculates the 11-bit frame check sequence of the binary value.
+    fn frame_check_sequence(binary: u128) -> u16 {
+        let bytes: Vec<u16> = (0..13).rev().map(|i| (binary >> (i * 8)) as u16 & 0xFF).collect();
+        let mut fcs = 0x07FF;
+
+        for (i, &byte) in bytes.iter().enumerate() {
+            // The two most significant bits of the first byte are always zero and are skipped.
+            let (mut data, bits) = if i == 0 { (byte << 5, 6) } else { (byte << 3, 8) };
+
+            for _ in 0..bits {
+                fcs = if (fcs ^ data) & 0x400 != 0 {
+                    (fcs << 1) ^ FCS_POLYNOMIAL
+                } else {
+                    fcs << 1
+                } & 0x07FF;
+                data <<= 1;
+            }
+        }
+
+        fcs
+    }
+
+    /// Builds the table of 13-bit characters with `n` bits set.
+    /// Characters are placed in pairs with their bit-reversal, and characters that are their own
+    /// bit-reversal are placed from the end of the table.
+    fn character_table(n: u32, len: usize) -> Vec<u16> {
+        let mut table = vec![0; len];
+        let mut lower = 0;
+        let mut upper = len - 1;
+
+        for c in 0..8192u16 {
+            let reverse = c.reverse_bits() >> 3;
+
+            if c.count_ones() != n || reverse < c {
+                continue;
+            }
+
+            if reverse == c {
+                table[upper] = c;
+                upper -= 1;
+            } else {
+                table[lower] = c;
+                table[lower + 1] = reverse;
+                lower += 2;
+            }
+        }
+
+        table
+    }
+
+    /// Converts the binary value into the 10 characters of the barcode.
+    fn characters(&self) -> [u16; 10] {
+        let mut binary = self.binary();
+        let fcs = IMB::frame_check_sequence(binary);
+        let mut codewords = [0u16; 10];
+
+        codewords[9] = (binary % 636) as u16 * 2;
+        binary /= 636;
+
+        for i in (1..9).rev() {
+            codewords[i] = (binary % 1365) as u16;
+            binary /= 1365;
+        }
+
+        codewords[0] = binary as u16;
+
+        if fcs & 0x400 != 0 {
+            codewords[0] += 659;
+        }
+
+        let five_of_13 = IMB::character_table(5, 1287);
+        let two_of_13 = IMB::character_table(2, 78);
+        let mut characters = [0u16; 10];
+
+        for (i, &codeword) in codewords.iter().enumerate() {
+            let c = match codeword {
+                n if n < 1287 => five_of_13[n as usize],
+                n => two_of_13[n as usize - 1287],
+            };
+
+            characters[i] = if fcs & (1 << i) != 0 { !c & 0x1FFF } else { c };
+        }
+
+        characters
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<State> of the 65 bars, from left to right.
+    pub fn encode(&self) -> Vec<State> {
+        let characters = self.characters();
+        let mut bars = [false; 130];
+
+        for (i, &bar) in BARS.iter().enumerate() {
+            if characters[i / 13] & (1 << (i % 13)) != 0 {
+                bars[bar as usize - 1] = true;
+            }
+        }
+
+        (0..65).map(|i| match (bars[i + 65], bars[i]) {
+                   (true, true) => State::Full,
+                   (true, false) => State::Ascender,
+                   (false, true) => State::Descender,
+                   (false, false) => State::Tracker,
+               })
+               .collect()
+    }
+
+    /// Returns the layout of the barcode, with rows for the ascenders, the tracker and the
+    /// descenders.
+    pub fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+impl Parse for IMB {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        20..31
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::imb::*;
+    use sym::layout::State;
+    use error::Error;
+
+    fn collapse_states(v: Vec<State>) -> String {
+        v.iter().map(|s| s.to_char()).collect()
+    }
+
+    #[test]
+    fn new_imb() {
+        let imb = IMB::new("0123456709498765432101234567891").unwrap();
+
+        assert_eq!(imb.tracking_code(), "01234567094987654321");
+        assert_eq!(imb.routing_code(), "01234567891");
+    }
+
+    #[test]
+    fn invalid_data_imb() {
+        assert_eq!(IMB::new("0123456709498765432A").err().unwrap(), Error::Character);
+        assert_eq!(IMB::new("05234567094987654321").err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_imb() {
+        assert_eq!(IMB::new("0123456709498765432").err().unwrap(), Error::Length);
+        assert_eq!(IMB::new("012345670949876543210").err().unwrap(), Error::Length);
+        assert_eq!(IMB::new("01234567094987654321012345678912").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn imb_encode() {
+        let imb1 = IMB::new("01234567094987654321").unwrap();
+        let imb2 = IMB::new("0123456709498765432101234").unwrap();
+        let imb3 = IMB::new("01234567094987654321012345678").unwrap();
+        let imb4 = IMB::new("0123456709498765432101234567891").unwrap();
+
+        assert_eq!(collapse_states(imb1.encode()), "ATTFATTDTTADTAATTDTDTATTDAFDDFADFDFTFFFFFTATFAAAATDFFTDAADFTFDTDT");
+        assert_eq!(collapse_states(imb2.encode()), "DTTAFADDTTFTDTFTFDTDDADADAFADFATDDFTAAAFDTTADFAAATDFDTDFADDDTDFFT");
+        assert_eq!(collapse_states(imb3.encode()), "ADFTTAFDTTTTFATTADTAAATFTFTATDAAAFDDADATATDTDTTDFDTDATADADTDFFTFA");
+        assert_eq!(collapse_states(imb4.encode()), "AADTFFDFTDADTAADAATFDTDDAAADDTDTTDAFADADDDTFFFDDTTTADFAAADFTDAADA");
+    }
+
+    #[test]
+    fn imb_layout() {
+        let layout = IMB::new("01234567094987654321").unwrap().layout();
+
+        assert_eq!(layout.rows.len(), 3);
+        assert_eq!(layout.width(), 65 * 2 - 1);
+        assert_eq!(layout.rows[0].modules[..8], [1, 0, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(layout.rows[1].modules[..8], [1, 0, 1, 0, 1, 0, 1, 0]);
+        assert_eq!(layout.rows[2].modules[..8], [0, 0, 0, 0, 0, 0, 1, 0]);
+    }
+}
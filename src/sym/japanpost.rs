@@ -0,0 +1,173 @@
+//! Encoder for Japan Post customer barcodes (Kasutama barcodes).
+//!
+//! Japan Post barcodes are 4-state barcodes encoding the 7 digit postal code followed by the
+//! address display number, in 20 characters plus a modulo-19 check digit, between a pair of start
+//! and stop bars.
+//!
+//! Digits and '-' are encoded directly. Letters are encoded as a control code followed by a digit:
+//! A-J as CC1, K-T as CC2 and U-Z as CC3, followed by the letter's position within its group.
+//! Unused characters are filled with CC4.
+
+use sym::Parse;
+use sym::layout::{Layout, State};
+use error::{Error, Result};
+use std::ops::Range;
+
+// The number of characters encoded, excluding the check digit.
+const LENGTH: usize = 20;
+
+// The values of the characters, where 0-9 are the digits, 10 is '-' and 11-18 are CC1-CC8.
+const HYPHEN: u8 = 10;
+const CC1: u8 = 11;
+const CC4: u8 = 14;
+
+// The bars of each character value, where 1 = full, 2 = ascender, 3 = descender, 4 = tracker.
+const CHARS: [[u8; 3]; 19] = [
+    [1, 4, 4], [1, 1, 4], [1, 3, 2], [3, 1, 2], [1, 2, 3], [1, 4, 1], [3, 2, 1], [2, 1, 3],
+    [2, 3, 1], [4, 1, 1], [4, 1, 4], [3, 2, 4], [3, 4, 2], [2, 3, 4], [4, 3, 2], [2, 4, 3],
+    [4, 2, 3], [4, 4, 1], [1, 1, 1],
+];
+
+// The start and stop bars.
+const START: [u8; 2] = [1, 3];
+const STOP: [u8; 2] = [3, 1];
+
+/// The Japan Post barcode type.
+#[derive(Debug)]
+pub struct JapanPost(Vec<u8>);
+
+impl JapanPost {
+    /// Creates a new barcode.
+    /// The data is the 7 digit postal code, followed by the address display number of digits,
+    /// '-' and uppercase letters. Each letter takes up two of the 20 characters.
+    /// Returns Result<JapanPost, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<JapanPost> {
+        let d = JapanPost::parse(data.as_ref())?;
+
+        if !d[..7].chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::Character);
+        }
+
+        let mut values = vec![];
+
+        for c in d.bytes() {
+            match c {
+                b'0'..=b'9' => values.push(c - b'0'),
+                b'-' => values.push(HYPHEN),
+                _ => {
+                    let i = c - b'A';
+                    values.extend(&[CC1 + i / 10, i % 10]);
+                }
+            }
+        }
+
+        if values.len() > LENGTH {
+            return Err(Error::Length);
+        }
+
+        values.resize(LENGTH, CC4);
+        Ok(JapanPost(values))
+    }
+
+    /// Calculates the check digit, which makes the sum of the character values a multiple of 19.
+    /// Returns the value of the check digit, where 10 is '-' and 11-18 are CC1-CC8.
+    pub fn check_digit(&self) -> u8 {
+        let sum: u32 = self.0.iter().map(|&v| u32::from(v)).sum();
+
+        ((19 - sum % 19) % 19) as u8
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<State> of the bars, from left to right, including the start and stop bars.
+    pub fn encode(&self) -> Vec<State> {
+        let mut bars = START.to_vec();
+
+        for &v in self.0.iter().chain(Some(self.check_digit()).iter()) {
+            bars.extend(&CHARS[v as usize]);
+        }
+
+        bars.extend(&STOP);
+        bars.iter()
+            .map(|&b| match b {
+                1 => State::Full,
+                2 => State::Ascender,
+                3 => State::Descender,
+                _ => State::Tracker,
+            })
+            .collect()
+    }
+
+    /// Returns the layout of the barcode, with rows for the ascenders, the tracker and the
+    /// descenders.
+    pub fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+impl Parse for JapanPost {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        7..20
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        "0123456789-ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::japanpost::*;
+    use sym::layout::State;
+    use error::Error;
+
+    fn collapse_states(v: Vec<State>) -> String {
+        v.iter().map(|s| s.to_char()).collect()
+    }
+
+    #[test]
+    fn new_japanpost() {
+        let japanpost = JapanPost::new("15400233-16-4-205");
+
+        assert!(japanpost.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_japanpost() {
+        assert_eq!(JapanPost::new("1540023a").err().unwrap(), Error::Character);
+        assert_eq!(JapanPost::new("154-0023").err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_japanpost() {
+        assert_eq!(JapanPost::new("154002").err().unwrap(), Error::Length);
+        assert_eq!(JapanPost::new("15400233-16-4-205-112").err().unwrap(), Error::Length);
+        // Each letter takes up two characters.
+        assert_eq!(JapanPost::new("1540023ABCDEFG").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn japanpost_check_digit() {
+        assert_eq!(JapanPost::new("15400233-16-4-205").unwrap().check_digit(), 6);
+        assert_eq!(JapanPost::new("1540023").unwrap().check_digit(), 12);
+        assert_eq!(JapanPost::new("0640038MOMOE").unwrap().check_digit(), 14);
+    }
+
+    #[test]
+    fn japanpost_encode() {
+        let japanpost1 = JapanPost::new("15400233-16-4-205").unwrap();
+        let japanpost2 = JapanPost::new("0640038MOMOE").unwrap();
+
+        assert_eq!(collapse_states(japanpost1.encode()), "FDFFTFTFFADFTTFTTFDADFADFATFTFFTDAFTFTFADTFTFDAFTTFTFTDATDATDADAFDF");
+        assert_eq!(collapse_states(japanpost2.encode()), "FDFTTDAFFADFTTFTTDFAADFDTAFDADTAFADDTAFDADTAFADDATFADTDATDATDATDADF");
+    }
+
+    #[test]
+    fn japanpost_layout() {
+        let layout = JapanPost::new("1540023").unwrap().layout();
+
+        assert_eq!(layout.rows.len(), 3);
+        assert_eq!(layout.width(), 67 * 2 - 1);
+    }
+}
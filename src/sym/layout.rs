@@ -0,0 +1,228 @@
+//! Multi-row barcode layouts.
+//!
+//! Most barcodes are encoded as a single row of modules, but some cannot be drawn that way, such
+//! as barcodes framed by bearer bars. A `Layout` describes a barcode as rows of modules stacked
+//! from top to bottom, each with a height relative to the other rows. The SVG and image
+//! generators can render layouts via their `generate_layout` methods.
+//!
+//! 4-state barcodes, such as postal barcodes, are encoded as a sequence of bar `State`s, which
+//! can be converted into a layout with rows for the ascenders, the tracker and the descenders.
+//! Likewise, height-modulated barcodes are encoded as a sequence of tall and short bar `Height`s.
+//!
+//! For example:
+//!
+//! ```rust
+//! use barcoders::sym::layout::*;
+//!
+//! // A barcode with a thin bearer bar above and below it.
+//! let layout = Layout::new(vec![Row::new(1, vec![1, 1, 1, 1, 1]),
+//!                               Row::new(8, vec![1, 0, 1, 1, 0]),
+//!                               Row::new(1, vec![1, 1, 1, 1, 1])]);
+//!
+//! assert_eq!(layout.width(), 5);
+//! assert_eq!(layout.height(), 10);
+//! ```
+
+/// The state of a bar in a 4-state barcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    /// A bar spanning the ascender, tracker and descender.
+    Full,
+    /// A bar spanning the ascender and tracker.
+    Ascender,
+    /// A bar spanning the tracker and descender.
+    Descender,
+    /// A bar spanning only the tracker.
+    Tracker,
+}
+
+impl State {
+    /// Returns the letter commonly used to denote the state (F, A, D or T).
+    pub fn to_char(self) -> char {
+        match self {
+            State::Full => 'F',
+            State::Ascender => 'A',
+            State::Descender => 'D',
+            State::Tracker => 'T',
+        }
+    }
+
+    fn has_ascender(self) -> bool {
+        self == State::Full || self == State::Ascender
+    }
+
+    fn has_descender(self) -> bool {
+        self == State::Full || self == State::Descender
+    }
+}
+
+/// The height of a bar in a height-modulated barcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Height {
+    /// A full-height bar.
+    Tall,
+    /// A short bar, 2/5 of the height of a tall bar.
+    Short,
+}
+
+impl Height {
+    /// Returns the character commonly used to denote the height ('|' or '.').
+    pub fn to_char(self) -> char {
+        match self {
+            Height::Tall => '|',
+            Height::Short => '.',
+        }
+    }
+}
+
+/// A row of modules in a barcode layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row {
+    /// The height of the row, relative to the other rows of the layout.
+    pub height: u32,
+    /// The modules of the row. 1 = bar, 0 = no bar.
+    pub modules: Vec<u8>,
+}
+
+impl Row {
+    /// Constructor.
+    pub fn new(height: u32, modules: Vec<u8>) -> Row {
+        Row { height, modules }
+    }
+}
+
+/// A barcode made up of one or more rows of modules.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    /// The rows of the barcode, from top to bottom.
+    pub rows: Vec<Row>,
+}
+
+impl Layout {
+    /// Constructor.
+    pub fn new(rows: Vec<Row>) -> Layout {
+        Layout { rows }
+    }
+
+    /// Returns the width of the layout in modules (the width of its widest row).
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|r| r.modules.len()).max().unwrap_or(0)
+    }
+
+    /// Returns the sum of the relative heights of the rows.
+    pub fn height(&self) -> u32 {
+        self.rows.iter().map(|r| r.height).sum()
+    }
+
+    /// Returns the vertical offset and height of each row when the layout is scaled to the
+    /// given total height.
+    pub fn scale(&self, height: u32) -> Vec<(u32, u32)> {
+        let total = u64::from(self.height().max(1));
+        let mut offset = 0;
+
+        self.rows
+            .iter()
+            .map(|r| {
+                let start = offset * u64::from(height) / total;
+                offset += u64::from(r.height);
+                let end = offset * u64::from(height) / total;
+
+                (start as u32, (end - start) as u32)
+            })
+            .collect()
+    }
+}
+
+impl<'a> From<&'a [u8]> for Layout {
+    /// Creates a single-row layout from encoded barcode modules.
+    fn from(modules: &'a [u8]) -> Layout {
+        Layout::new(vec![Row::new(1, modules.to_vec())])
+    }
+}
+
+impl<'a> From<&'a [State]> for Layout {
+    /// Creates a layout from 4-state bars, with rows of equal height for the ascenders, the
+    /// tracker and the descenders. Bars and the spaces between them are each one module wide.
+    fn from(states: &'a [State]) -> Layout {
+        let row = |bar: &dyn Fn(State) -> bool| {
+            let mut modules = vec![];
+
+            for (i, &state) in states.iter().enumerate() {
+                if i > 0 {
+                    modules.push(0);
+                }
+
+                modules.push(bar(state) as u8);
+            }
+
+            Row::new(1, modules)
+        };
+
+        Layout::new(vec![row(&|s| s.has_ascender()), row(&|_| true), row(&|s| s.has_descender())])
+    }
+}
+
+impl<'a> From<&'a [Height]> for Layout {
+    /// Creates a layout from tall and short bars, with a row for the top of the tall bars above a
+    /// row for all bars, such that short bars are 2/5 of the height of tall bars. Bars and the
+    /// spaces between them are each one module wide.
+    fn from(heights: &'a [Height]) -> Layout {
+        let mut top = vec![];
+        let mut bottom = vec![];
+
+        for (i, &height) in heights.iter().enumerate() {
+            if i > 0 {
+                top.push(0);
+                bottom.push(0);
+            }
+
+            top.push((height == Height::Tall) as u8);
+            bottom.push(1);
+        }
+
+        Layout::new(vec![Row::new(3, top), Row::new(2, bottom)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::layout::*;
+
+    #[test]
+    fn layout_from_modules() {
+        let layout = Layout::from(&[1, 0, 1, 1][..]);
+
+        assert_eq!(layout.rows, vec![Row::new(1, vec![1, 0, 1, 1])]);
+        assert_eq!(layout.width(), 4);
+        assert_eq!(layout.height(), 1);
+    }
+
+    #[test]
+    fn layout_scale() {
+        let layout = Layout::new(vec![Row::new(1, vec![1, 1]),
+                                      Row::new(2, vec![1, 0]),
+                                      Row::new(1, vec![1, 1])]);
+
+        assert_eq!(layout.scale(100), vec![(0, 25), (25, 50), (75, 25)]);
+        assert_eq!(layout.scale(10), vec![(0, 2), (2, 5), (7, 3)]);
+    }
+
+    #[test]
+    fn layout_from_states() {
+        let layout = Layout::from(&[State::Full, State::Ascender, State::Descender, State::Tracker][..]);
+
+        assert_eq!(layout.rows, vec![Row::new(1, vec![1, 0, 1, 0, 0, 0, 0]),
+                                     Row::new(1, vec![1, 0, 1, 0, 1, 0, 1]),
+                                     Row::new(1, vec![1, 0, 0, 0, 1, 0, 0])]);
+        assert_eq!(State::Descender.to_char(), 'D');
+    }
+
+    #[test]
+    fn layout_from_heights() {
+        let layout = Layout::from(&[Height::Tall, Height::Short, Height::Tall][..]);
+
+        assert_eq!(layout.rows, vec![Row::new(3, vec![1, 0, 0, 0, 1]),
+                                     Row::new(2, vec![1, 0, 1, 0, 1])]);
+        assert_eq!(Height::Short.to_char(), '.');
+    }
+}
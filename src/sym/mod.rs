@@ -12,27 +12,428 @@
 //! ```
 //! Each encoder accepts a `String` to be encoded. Valid data is barcode-specific and thus
 //! constructors return an Option<T>.
+//!
+//! Every symbology also implements the `Barcode` trait, so they can be used interchangeably:
+//!
+//! ```rust
+//! use barcoders::sym::Barcode;
+//! use barcoders::sym::ean13::*;
+//! use barcoders::sym::code39::*;
+//!
+//! let barcodes: Vec<Box<dyn Barcode>> = vec![Box::new(EAN13::new("750103131130").unwrap()),
+//!                                            Box::new(Code39::new("1ISTHELONELIEST").unwrap())];
+//!
+//! for barcode in &barcodes {
+//!     println!("{}: {}", barcode.symbology(), barcode.text());
+//! }
+//! ```
+//!
+//! When the symbology is only known at runtime, use the `Symbology` enum:
+//!
+//! ```rust
+//! use barcoders::sym::{self, Symbology};
+//!
+//! let symbology: Symbology = "ean13".parse().unwrap();
+//! let encoded = sym::encode(symbology, "750103131130").unwrap();
+//! ```
+//!
+//! Symbologies with optional check characters choose them via a constructor taking the data and
+//! the options, such as `MSI::with_check`, `TF::interleaved_with_check`, `Code11::with_options`
+//! and `Codabar::with_options`.
 
 pub mod ean13;
 pub mod ean8;
+pub mod upce;
 pub mod ean_supp;
 pub mod code39;
 pub mod code93;
 pub mod code11;
 pub mod code128;
+pub mod gs1_128;
 pub mod codabar;
 pub mod tf;
+pub mod msi;
+pub mod pharmacode;
+pub mod imb;
+pub mod rm4scc;
+pub mod postnet;
+pub mod auspost;
+pub mod japanpost;
+pub mod databar;
+pub mod layout;
 mod helpers;
 
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 use std::iter::Iterator;
-use error::Error;
+use std::fmt;
+use std::str::FromStr;
+use error::{Error, Result};
+use self::ean13::{EAN13, Bookland};
+use self::ean8::EAN8;
+use self::upce::UPCE;
+use self::ean_supp::{EANSUPP, WithAddOn};
+use self::code39::Code39;
+use self::code93::Code93;
+use self::code11::Code11;
+use self::code128::Code128;
+use self::gs1_128::GS1128;
+use self::codabar::Codabar;
+use self::tf::{TF, TFVariantCheck, ITF14};
+use self::msi::MSI;
+use self::pharmacode::Pharmacode;
+use self::databar::{DataBar, DataBarLimited};
+use self::layout::Layout;
+
+/// Behaviour common to all barcode symbologies.
+pub trait Barcode {
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Returns the name of the symbology.
+    fn symbology(&self) -> &'static str;
+
+    /// Returns the data held by the barcode, excluding any computed check characters.
+    fn data(&self) -> String;
+
+    /// Returns the check character(s) computed during encoding, if any.
+    fn checksum(&self) -> Option<String>;
+
+    /// Returns the human-readable text that is usually printed beneath the barcode.
+    fn text(&self) -> String {
+        match self.checksum() {
+            Some(c) => format!("{}{}", self.data(), c),
+            None => self.data(),
+        }
+    }
+
+    /// Returns the layout of the barcode.
+    /// By default this is a single row of the encoded modules, but barcodes that are not drawn
+    /// as a single row, such as stacked GS1 DataBar barcodes, return all of their rows.
+    fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+// The valid range of wide-to-narrow ratios, in half-modules (2.0 - 3.0).
+const RATIOS: RangeInclusive<u8> = 4..=6;
+
+/// The ratio of the width of wide elements to narrow elements in two-width symbologies, in
+/// half-modules (4 = 2.0:1, 5 = 2.5:1, 6 = 3.0:1).
+///
+/// When the ratio is a whole number, narrow elements are encoded as 1 module. Otherwise, narrow
+/// elements are encoded as 2 modules (so 2.5:1 is encoded as 2 and 5 modules).
+///
+/// Code39, Codabar, Code11 and 2-of-5 barcodes can be encoded with a given ratio via their
+/// `encode_with_ratio` methods.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ratio(u8);
+
+impl Ratio {
+    /// Creates a new ratio from the number of half-modules.
+    /// Returns Error::Ratio if the ratio is not between 2.0:1 and 3.0:1.
+    pub fn new(half_modules: u8) -> Result<Ratio> {
+        match half_modules {
+            h if RATIOS.contains(&h) => Ok(Ratio(h)),
+            _ => Err(Error::Ratio),
+        }
+    }
+
+    /// Returns the widths (in modules) of the narrow and wide elements.
+    pub fn widths(self) -> (usize, usize) {
+        match self.0 % 2 {
+            0 => (1, self.0 as usize / 2),
+            _ => (2, self.0 as usize),
+        }
+    }
+}
+
+/// The available symbologies, for choosing an encoder at runtime.
+///
+/// The data of the EAN/UPC symbologies with an add-on is the main data and the add-on data,
+/// separated by a space, e.g. "750103131130 12".
+///
+/// Only symbologies that encode to a single row of bars and spaces are included. Two-track
+/// Pharmacode, and the 4-state and height-modulated postal barcodes, encode the tracks or heights
+/// of their bars instead, so must be created from their own types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Symbology {
+    /// EAN-13.
+    EAN13,
+    /// UPC-A (an EAN-13 starting with 0).
+    UPCA,
+    /// JAN (an EAN-13 using number system 45 or 49).
+    JAN,
+    /// Bookland (an EAN-13 using number system 978 or 979).
+    Bookland,
+    /// EAN-8.
+    EAN8,
+    /// UPC-E.
+    UPCE,
+    /// EAN-2 supplemental.
+    EAN2,
+    /// EAN-5 supplemental.
+    EAN5,
+    /// EAN-13 with an EAN-2 or EAN-5 add-on.
+    EAN13AddOn,
+    /// UPC-A with an EAN-2 or EAN-5 add-on.
+    UPCAAddOn,
+    /// EAN-8 with an EAN-2 or EAN-5 add-on.
+    EAN8AddOn,
+    /// UPC-E with an EAN-2 or EAN-5 add-on.
+    UPCEAddOn,
+    /// Code11 (USD-8).
+    Code11,
+    /// Code39.
+    Code39,
+    /// Code39 with a modulo-43 check character.
+    Code39Checksum,
+    /// Code39 Full ASCII.
+    Code39FullASCII,
+    /// Code93.
+    Code93,
+    /// Code93 Full ASCII.
+    Code93FullASCII,
+    /// Code128.
+    Code128,
+    /// GS1-128 (UCC/EAN-128).
+    GS1128,
+    /// Codabar.
+    Codabar,
+    /// Interleaved 2-of-5.
+    ITF,
+    /// ITF-14.
+    ITF14,
+    /// Standard 2-of-5.
+    STF,
+    /// Industrial 2-of-5.
+    Industrial2of5,
+    /// Matrix 2-of-5.
+    Matrix2of5,
+    /// IATA 2-of-5.
+    IATA2of5,
+    /// Datalogic 2-of-5.
+    Datalogic2of5,
+    /// COOP 2-of-5.
+    COOP2of5,
+    /// MSI Plessey.
+    MSI,
+    /// One-track Pharmacode.
+    Pharmacode,
+    /// GS1 DataBar Omnidirectional (RSS-14).
+    DataBar,
+    /// GS1 DataBar Truncated. Its reduced height is only available from `layout`.
+    DataBarTruncated,
+    /// GS1 DataBar Stacked. Its rows are only available from `layout`.
+    DataBarStacked,
+    /// GS1 DataBar Stacked Omnidirectional. Its rows are only available from `layout`.
+    DataBarStackedOmnidirectional,
+    /// GS1 DataBar Limited.
+    DataBarLimited,
+}
+
+// The symbologies matched by name when parsing. Names are matched ignoring case and any '-', '_'
+// or ' '.
+const SYMBOLOGIES: [Symbology; 36] = [
+    Symbology::EAN13, Symbology::UPCA, Symbology::JAN, Symbology::Bookland, Symbology::EAN8,
+    Symbology::UPCE, Symbology::EAN2, Symbology::EAN5, Symbology::EAN13AddOn, Symbology::UPCAAddOn,
+    Symbology::EAN8AddOn, Symbology::UPCEAddOn, Symbology::Code11, Symbology::Code39,
+    Symbology::Code39Checksum, Symbology::Code39FullASCII, Symbology::Code93,
+    Symbology::Code93FullASCII, Symbology::Code128, Symbology::GS1128, Symbology::Codabar,
+    Symbology::ITF, Symbology::ITF14, Symbology::STF, Symbology::Industrial2of5,
+    Symbology::Matrix2of5, Symbology::IATA2of5, Symbology::Datalogic2of5, Symbology::COOP2of5,
+    Symbology::MSI, Symbology::Pharmacode, Symbology::DataBar, Symbology::DataBarTruncated,
+    Symbology::DataBarStacked, Symbology::DataBarStackedOmnidirectional, Symbology::DataBarLimited,
+];
+
+// Alternative names accepted when parsing a symbology.
+const ALIASES: [(&str, Symbology); 20] = [
+    ("usd8", Symbology::Code11), ("interleaved2of5", Symbology::ITF), ("i2of5", Symbology::ITF),
+    ("standard2of5", Symbology::STF), ("s2of5", Symbology::STF),
+    ("ean128", Symbology::GS1128), ("ucc128", Symbology::GS1128), ("gtin14", Symbology::ITF14),
+    ("code39extended", Symbology::Code39FullASCII), ("code93extended", Symbology::Code93FullASCII),
+    ("msiplessey", Symbology::MSI), ("modifiedplessey", Symbology::MSI),
+    ("laetus", Symbology::Pharmacode), ("gs1databar", Symbology::DataBar),
+    ("databaromnidirectional", Symbology::DataBar), ("rss14", Symbology::DataBar),
+    ("rss14truncated", Symbology::DataBarTruncated), ("rss14stacked", Symbology::DataBarStacked),
+    ("rss14stackedomnidirectional", Symbology::DataBarStackedOmnidirectional),
+    ("rsslimited", Symbology::DataBarLimited),
+];
+
+impl Symbology {
+    // Returns the canonical name of the symbology, which is also accepted when parsing.
+    fn name(self) -> &'static str {
+        match self {
+            Symbology::EAN13 => "ean13",
+            Symbology::UPCA => "upca",
+            Symbology::JAN => "jan",
+            Symbology::Bookland => "bookland",
+            Symbology::EAN8 => "ean8",
+            Symbology::UPCE => "upce",
+            Symbology::EAN2 => "ean2",
+            Symbology::EAN5 => "ean5",
+            Symbology::EAN13AddOn => "ean13addon",
+            Symbology::UPCAAddOn => "upcaaddon",
+            Symbology::EAN8AddOn => "ean8addon",
+            Symbology::UPCEAddOn => "upceaddon",
+            Symbology::Code11 => "code11",
+            Symbology::Code39 => "code39",
+            Symbology::Code39Checksum => "code39checksum",
+            Symbology::Code39FullASCII => "code39fullascii",
+            Symbology::Code93 => "code93",
+            Symbology::Code93FullASCII => "code93fullascii",
+            Symbology::Code128 => "code128",
+            Symbology::GS1128 => "gs1128",
+            Symbology::Codabar => "codabar",
+            Symbology::ITF => "itf",
+            Symbology::ITF14 => "itf14",
+            Symbology::STF => "stf",
+            Symbology::Industrial2of5 => "industrial2of5",
+            Symbology::Matrix2of5 => "matrix2of5",
+            Symbology::IATA2of5 => "iata2of5",
+            Symbology::Datalogic2of5 => "datalogic2of5",
+            Symbology::COOP2of5 => "coop2of5",
+            Symbology::MSI => "msi",
+            Symbology::Pharmacode => "pharmacode",
+            Symbology::DataBar => "databar",
+            Symbology::DataBarTruncated => "databartruncated",
+            Symbology::DataBarStacked => "databarstacked",
+            Symbology::DataBarStackedOmnidirectional => "databarstackedomnidirectional",
+            Symbology::DataBarLimited => "databarlimited",
+        }
+    }
+
+    /// Creates a new barcode of this symbology.
+    /// Returns Result<Box<dyn Barcode>, Error> indicating parse success.
+    pub fn build<T: AsRef<str>>(self, data: T) -> Result<Box<dyn Barcode>> {
+        let data = data.as_ref();
+
+        match self {
+            Symbology::EAN13 => EAN13::new(data).map(boxed),
+            Symbology::UPCA => prefixed_ean13(data, &["0"]).map(boxed),
+            Symbology::JAN => prefixed_ean13(data, &["45", "49"]).map(boxed),
+            Symbology::Bookland => Bookland::new(data).map(boxed),
+            Symbology::EAN8 => EAN8::new(data).map(boxed),
+            Symbology::UPCE => UPCE::new(data).map(boxed),
+            Symbology::EAN2 |
+            Symbology::EAN5 => {
+                match (self, data.len()) {
+                    (Symbology::EAN2, 2) | (Symbology::EAN5, 5) => EANSUPP::new(data).map(boxed),
+                    _ => Err(Error::Length),
+                }
+            }
+            Symbology::EAN13AddOn |
+            Symbology::UPCAAddOn |
+            Symbology::EAN8AddOn |
+            Symbology::UPCEAddOn => {
+                let (main, addon) = split_addon(data)?;
+
+                match self {
+                    Symbology::EAN13AddOn => EAN13::with_addon(main, addon).map(boxed),
+                    Symbology::UPCAAddOn => prefixed_ean13(main, &["0"])
+                        .and_then(|b| WithAddOn::new(b, addon))
+                        .map(boxed),
+                    Symbology::EAN8AddOn => EAN8::with_addon(main, addon).map(boxed),
+                    _ => UPCE::with_addon(main, addon).map(boxed),
+                }
+            }
+            Symbology::Code11 => Code11::new(data).map(boxed),
+            Symbology::Code39 => Code39::new(data).map(boxed),
+            Symbology::Code39Checksum => Code39::with_checksum(data).map(boxed),
+            Symbology::Code39FullASCII => Code39::full_ascii(data).map(boxed),
+            Symbology::Code93 => Code93::new(data).map(boxed),
+            Symbology::Code93FullASCII => Code93::full_ascii(data).map(boxed),
+            Symbology::Code128 => Code128::new(data).map(boxed),
+            Symbology::GS1128 => GS1128::new(data).map(boxed),
+            Symbology::Codabar => Codabar::new(data).map(boxed),
+            Symbology::ITF => TF::interleaved(data).map(boxed),
+            Symbology::ITF14 => ITF14::new(data).map(boxed),
+            Symbology::STF => TF::standard(data).map(boxed),
+            Symbology::Industrial2of5 => TF::industrial(data, TFVariantCheck::None).map(boxed),
+            Symbology::Matrix2of5 => TF::matrix(data, TFVariantCheck::None).map(boxed),
+            Symbology::IATA2of5 => TF::iata(data, TFVariantCheck::None).map(boxed),
+            Symbology::Datalogic2of5 => TF::datalogic(data, TFVariantCheck::None).map(boxed),
+            Symbology::COOP2of5 => TF::coop(data, TFVariantCheck::None).map(boxed),
+            Symbology::MSI => MSI::new(data).map(boxed),
+            Symbology::Pharmacode => Pharmacode::new(data).map(boxed),
+            Symbology::DataBar => DataBar::new(data).map(boxed),
+            Symbology::DataBarTruncated => DataBar::truncated(data).map(boxed),
+            Symbology::DataBarStacked => DataBar::stacked(data).map(boxed),
+            Symbology::DataBarStackedOmnidirectional => {
+                DataBar::stacked_omnidirectional(data).map(boxed)
+            }
+            Symbology::DataBarLimited => DataBarLimited::new(data).map(boxed),
+        }
+    }
+}
+
+impl fmt::Display for Symbology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Symbology {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Symbology> {
+        let name: String = s.chars()
+                            .filter(|c| !['-', '_', ' '].contains(c))
+                            .flat_map(|c| c.to_lowercase())
+                            .collect();
+        let symbology = SYMBOLOGIES.iter()
+                                   .find(|s| s.name() == name)
+                                   .cloned();
+        let alias = ALIASES.iter()
+                           .find(|a| a.0 == name)
+                           .map(|a| a.1);
+
+        symbology.or(alias).ok_or(Error::Symbology)
+    }
+}
+
+/// Creates and encodes a barcode of the given symbology.
+/// Returns Result<Vec<u8>, Error> indicating parse success.
+pub fn encode<T: AsRef<str>>(symbology: Symbology, data: T) -> Result<Vec<u8>> {
+    symbology.build(data).map(|b| b.encode())
+}
+
+/// Creates a barcode of the given symbology and returns its layout.
+/// Returns Result<Layout, Error> indicating parse success.
+pub fn layout<T: AsRef<str>>(symbology: Symbology, data: T) -> Result<Layout> {
+    symbology.build(data).map(|b| b.layout())
+}
+
+fn boxed<B: Barcode + 'static>(barcode: B) -> Box<dyn Barcode> {
+    Box::new(barcode)
+}
+
+// Splits the data of a barcode with an add-on into the main and add-on data, separated by a space.
+fn split_addon(data: &str) -> Result<(&str, &str)> {
+    let mut parts = data.splitn(2, ' ');
+
+    match (parts.next(), parts.next()) {
+        (Some(main), Some(addon)) => Ok((main, addon)),
+        _ => Err(Error::Length),
+    }
+}
+
+// Creates an EAN-13 barcode, checking that the data starts with one of the given number systems.
+fn prefixed_ean13(data: &str, prefixes: &[&str]) -> Result<EAN13> {
+    let ean13 = EAN13::new(data)?;
+
+    if !prefixes.iter().any(|p| data.starts_with(p)) {
+        return Err(Error::Character);
+    }
+
+    Ok(ean13)
+}
 
 trait Parse {
     fn valid_chars() -> Vec<char>;
     fn valid_len() -> Range<u32>;
 
-    fn parse(data: &str) -> Result<&str, Error> {
+    fn parse(data: &str) -> Result<&str> {
         let valid_chars = Self::valid_chars();
         let valid_len = Self::valid_len();
         let data_len = data.len() as u32;
@@ -54,3 +455,105 @@ trait Parse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sym::*;
+    use error::Error;
+
+    #[test]
+    fn symbology_from_str() {
+        assert_eq!("ean13".parse(), Ok(Symbology::EAN13));
+        assert_eq!("EAN-13".parse(), Ok(Symbology::EAN13));
+        assert_eq!("Code 128".parse(), Ok(Symbology::Code128));
+        assert_eq!("itf".parse(), Ok(Symbology::ITF));
+        assert_eq!("USD-8".parse(), Ok(Symbology::Code11));
+        assert_eq!("EAN-128".parse(), Ok(Symbology::GS1128));
+        assert_eq!("GS1 DataBar".parse(), Ok(Symbology::DataBar));
+        assert_eq!("RSS-14 Stacked".parse(), Ok(Symbology::DataBarStacked));
+        assert_eq!("RSS Limited".parse(), Ok(Symbology::DataBarLimited));
+        assert_eq!("qrcode".parse::<Symbology>(), Err(Error::Symbology));
+        assert_eq!("isbn".parse::<Symbology>(), Err(Error::Symbology));
+    }
+
+    #[test]
+    fn symbology_display_roundtrip() {
+        for &symbology in SYMBOLOGIES.iter() {
+            assert_eq!(symbology.to_string().parse(), Ok(symbology));
+        }
+
+        assert_eq!(Symbology::EAN13.to_string(), "ean13");
+        assert_eq!(Symbology::DataBarLimited.to_string(), "databarlimited");
+    }
+
+    #[test]
+    fn symbology_encode() {
+        let ean13 = EAN13::new("750103131130").unwrap();
+        let itf = TF::interleaved("1234567").unwrap();
+        let databar = DataBar::truncated("0001234567890").unwrap();
+
+        assert_eq!(encode(Symbology::EAN13, "750103131130").unwrap(), ean13.encode());
+        assert_eq!(encode(Symbology::ITF, "1234567").unwrap(), itf.encode());
+        assert_eq!(encode(Symbology::DataBarTruncated, "0001234567890").unwrap(), databar.encode());
+        assert_eq!(encode(Symbology::Code39, "1212s").err().unwrap(), Error::Character);
+        assert_eq!(encode(Symbology::EAN5, "12").err().unwrap(), Error::Length);
+        assert_eq!(encode(Symbology::DataBarLimited, "2001234567890").err().unwrap(),
+                   Error::Character);
+    }
+
+    #[test]
+    fn symbology_layout() {
+        let stacked = DataBar::stacked("0001234567890").unwrap();
+        let itf14 = ITF14::new("1540014128876").unwrap();
+
+        assert_eq!(layout(Symbology::DataBarStacked, "0001234567890").unwrap(), stacked.layout());
+        assert_ne!(layout(Symbology::DataBarStacked, "0001234567890").unwrap(),
+                   layout(Symbology::DataBar, "0001234567890").unwrap());
+        assert_eq!(layout(Symbology::ITF14, "1540014128876").unwrap(), itf14.layout());
+        assert_eq!(layout(Symbology::EAN8, "5512345").unwrap().rows.len(), 1);
+    }
+
+    #[test]
+    fn ratio() {
+        assert_eq!(Ratio::new(4).unwrap().widths(), (1, 2));
+        assert_eq!(Ratio::new(5).unwrap().widths(), (2, 5));
+        assert_eq!(Ratio::new(6).unwrap().widths(), (1, 3));
+        assert_eq!(Ratio::new(3).err().unwrap(), Error::Ratio);
+        assert_eq!(Ratio::new(7).err().unwrap(), Error::Ratio);
+    }
+
+    #[test]
+    fn symbology_build() {
+        let barcode = Symbology::Code39Checksum.build("1234").unwrap();
+
+        assert_eq!(barcode.symbology(), "Code39");
+        assert_eq!(barcode.text(), "1234A");
+    }
+
+    #[test]
+    fn symbology_build_with_addon() {
+        let ean13 = Symbology::EAN13AddOn.build("750103131130 12").unwrap();
+        let upce = Symbology::UPCEAddOn.build("0123456 51234").unwrap();
+
+        assert_eq!(ean13.text(), "7501031311309 12");
+        assert_eq!(ean13.encode(), EAN13::with_addon("750103131130", "12").unwrap().encode());
+        assert_eq!(upce.encode(), UPCE::with_addon("0123456", "51234").unwrap().encode());
+        assert!(Symbology::EAN8AddOn.build("5512345 12").is_ok());
+        assert!(Symbology::UPCAAddOn.build("012345612345 12").is_ok());
+        assert_eq!(Symbology::UPCAAddOn.build("750103131130 12").err().unwrap(), Error::Character);
+        assert_eq!(Symbology::EAN13AddOn.build("750103131130").err().unwrap(), Error::Length);
+        assert_eq!(Symbology::EAN13AddOn.build("750103131130 123").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn symbology_build_ean13_number_systems() {
+        assert!(Symbology::UPCA.build("012345612345").is_ok());
+        assert!(Symbology::JAN.build("453456123456").is_ok());
+        assert!(Symbology::JAN.build("493456123456").is_ok());
+        assert!(Symbology::Bookland.build("979345612345").is_ok());
+        assert_eq!(Symbology::UPCA.build("750103131130").err().unwrap(), Error::Character);
+        assert_eq!(Symbology::JAN.build("750103131130").err().unwrap(), Error::Character);
+        assert_eq!(Symbology::Bookland.build("977345612345").err().unwrap(), Error::Character);
+        assert_eq!(Symbology::UPCA.build("01234561234").err().unwrap(), Error::Length);
+    }
+}
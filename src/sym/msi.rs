@@ -0,0 +1,247 @@
+//! Encoder for MSI Plessey barcodes.
+//!
+//! MSI (also known as Modified Plessey) is able to encode all of the decimal digits. It is mainly
+//! used for labelling warehouse shelves and inventory.
+//!
+//! MSI is a continuous, variable-length symbology. Each digit is encoded as four binary-coded
+//! decimal bits. By default a single modulo-10 check digit is appended, but any of the common
+//! check digit variants can be chosen via `MSI::with_check`.
+
+use sym::{Barcode, Parse};
+use error::Result;
+use std::ops::Range;
+use std::char;
+
+// Each binary-coded decimal bit is a bar followed by a space, one narrow and one wide.
+const ZERO: [u8; 3] = [1,0,0];
+const ONE: [u8; 3] = [1,1,0];
+
+// MSI barcodes must start and end with special characters.
+const START: [u8; 3] = [1,1,0];
+const STOP: [u8; 4] = [1,0,0,1];
+
+/// The check digit variants of MSI barcodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MSICheck {
+    /// No check digit.
+    None,
+    /// A modulo-10 check digit.
+    Mod10,
+    /// A modulo-11 check digit, using the IBM weights (2 to 7).
+    Mod11IBM,
+    /// A modulo-11 check digit, using the NCR weights (2 to 9).
+    Mod11NCR,
+    /// A modulo-10 check digit, followed by a second modulo-10 check digit.
+    Mod10Mod10,
+    /// A modulo-11 check digit (IBM weights), followed by a modulo-10 check digit.
+    Mod11Mod10,
+}
+
+/// The MSI barcode type.
+#[derive(Debug)]
+pub struct MSI {
+    digits: Vec<u8>,
+    check: MSICheck,
+}
+
+impl MSI {
+    /// Creates a new barcode with a modulo-10 check digit.
+    /// Returns Result<MSI, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<MSI> {
+        MSI::with_check(data, MSICheck::Mod10)
+    }
+
+    /// Creates a new barcode with the given check digit variant.
+    /// Returns Result<MSI, Error> indicating parse success.
+    pub fn with_check<T: AsRef<str>>(data: T, check: MSICheck) -> Result<MSI> {
+        MSI::parse(data.as_ref()).map(|d| {
+            MSI {
+                digits: d.chars()
+                         .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                         .collect(),
+                check,
+            }
+        })
+    }
+
+    /// Returns the check digit variant of the barcode.
+    pub fn check(&self) -> MSICheck {
+        self.check
+    }
+
+    /// Calculates a check digit using the modulo-10 (Luhn) algorithm.
+    fn modulo_10(data: &[u8]) -> u8 {
+        let sum: u32 = data.iter()
+                           .rev()
+                           .enumerate()
+                           .map(|(i, &d)| match i % 2 {
+                               0 => u32::from(d * 2 / 10 + d * 2 % 10),
+                               _ => u32::from(d),
+                           })
+                           .sum();
+
+        ((10 - sum % 10) % 10) as u8
+    }
+
+    /// Calculates the check digit(s) using a weighted modulo-11 algorithm.
+    /// A check value of 10 is encoded as the two digits "10".
+    fn modulo_11(data: &[u8], max_weight: u32) -> Vec<u8> {
+        let sum: u32 = data.iter()
+                           .rev()
+                           .enumerate()
+                           .map(|(i, &d)| (i as u32 % (max_weight - 1) + 2) * u32::from(d))
+                           .sum();
+
+        match (11 - sum % 11) % 11 {
+            10 => vec![1, 0],
+            c => vec![c as u8],
+        }
+    }
+
+    /// Calculates the check digits of the barcode, according to its check digit variant.
+    fn check_digits(&self) -> Vec<u8> {
+        let mut check = match self.check {
+            MSICheck::None => vec![],
+            MSICheck::Mod10 | MSICheck::Mod10Mod10 => vec![MSI::modulo_10(&self.digits[..])],
+            MSICheck::Mod11IBM | MSICheck::Mod11Mod10 => MSI::modulo_11(&self.digits[..], 7),
+            MSICheck::Mod11NCR => MSI::modulo_11(&self.digits[..], 9),
+        };
+
+        if let MSICheck::Mod10Mod10 | MSICheck::Mod11Mod10 = self.check {
+            let mut data = self.digits.clone();
+            data.extend(&check);
+            check.push(MSI::modulo_10(&data[..]));
+        }
+
+        check
+    }
+
+    fn push_encoding(&self, into: &mut Vec<u8>, d: u8) {
+        for bit in (0..4).rev() {
+            match (d >> bit) & 1 {
+                0 => into.extend(&ZERO),
+                _ => into.extend(&ONE),
+            }
+        }
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let mut enc = vec![];
+
+        for &d in self.digits.iter().chain(self.check_digits().iter()) {
+            self.push_encoding(&mut enc, d);
+        }
+
+        enc
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of encoded binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut enc = START.to_vec();
+
+        enc.extend(self.payload());
+        enc.extend(&STOP);
+        enc
+    }
+}
+
+impl Parse for MSI {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    /// MSI barcodes are variable-length.
+    fn valid_len() -> Range<u32> {
+        1..256
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+impl Barcode for MSI {
+    fn encode(&self) -> Vec<u8> {
+        MSI::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "MSI"
+    }
+
+    fn data(&self) -> String {
+        self.digits.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        match self.check_digits() {
+            ref c if c.is_empty() => None,
+            c => Some(c.iter().map(|d| d.to_string()).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::msi::*;
+    use sym::Barcode;
+    use error::Error;
+    use std::char;
+
+    fn collapse_vec(v: Vec<u8>) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn new_msi() {
+        let msi = MSI::new("1234567");
+
+        assert!(msi.is_ok());
+        assert_eq!(msi.unwrap().check(), MSICheck::Mod10);
+    }
+
+    #[test]
+    fn invalid_data_msi() {
+        let msi = MSI::new("1234A");
+
+        assert_eq!(msi.err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_msi() {
+        let msi = MSI::new("");
+
+        assert_eq!(msi.err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn msi_encode() {
+        let msi1 = MSI::with_check("1234", MSICheck::None).unwrap();
+        let msi2 = MSI::new("1234").unwrap();
+
+        assert_eq!(collapse_vec(msi1.encode()), "1101001001001101001001101001001001101101001101001001001");
+        assert_eq!(collapse_vec(msi2.encode()), "1101001001001101001001101001001001101101001101001001001101001001001");
+    }
+
+    #[test]
+    fn msi_check_digits() {
+        let msi = |check| MSI::with_check("1234567", check).unwrap();
+
+        assert_eq!(msi(MSICheck::None).checksum(), None);
+        assert_eq!(msi(MSICheck::Mod10).checksum(), Some("4".to_owned()));
+        assert_eq!(msi(MSICheck::Mod11IBM).checksum(), Some("4".to_owned()));
+        assert_eq!(msi(MSICheck::Mod11NCR).checksum(), Some("9".to_owned()));
+        assert_eq!(msi(MSICheck::Mod10Mod10).checksum(), Some("41".to_owned()));
+        assert_eq!(msi(MSICheck::Mod11Mod10).checksum(), Some("41".to_owned()));
+    }
+
+    #[test]
+    fn msi_as_barcode() {
+        let msi = MSI::with_check("1205", MSICheck::Mod11NCR).unwrap();
+
+        assert_eq!(msi.symbology(), "MSI");
+        assert_eq!(msi.data(), "1205");
+        assert_eq!(msi.checksum(), Some("10".to_owned()));
+        assert_eq!(msi.text(), "120510");
+    }
+}
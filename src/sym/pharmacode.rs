@@ -0,0 +1,303 @@
+//! Encoder for Laetus Pharmacode barcodes.
+//!
+//! Pharmacode is used in the pharmaceutical industry as a packaging control code. It encodes a
+//! single integer and has no check digit.
+//!
+//! One-track Pharmacode encodes values from 3 to 131070 as a row of narrow and wide bars.
+//!
+//! Two-track Pharmacode encodes values from 4 to 64570080 as bars that occupy the top track, the
+//! bottom track or both tracks. As the bars have different heights, two-track barcodes are
+//! described by their `Layout` rather than a single row of modules.
+
+use sym::{Barcode, Parse};
+use sym::layout::{Layout, Row};
+use error::{Error, Result};
+use std::ops::{Range, RangeInclusive};
+use std::char;
+
+const ONE_TRACK_VALUES: RangeInclusive<u32> = 3..=131_070;
+const TWO_TRACK_VALUES: RangeInclusive<u32> = 4..=64_570_080;
+
+/// The One-track Pharmacode barcode type.
+#[derive(Copy, Clone, Debug)]
+pub struct Pharmacode {
+    value: u32,
+    narrow: u8,
+    wide: u8,
+    space: u8,
+}
+
+impl Pharmacode {
+    /// Creates a new barcode.
+    /// The narrow bars, wide bars and spaces are 1, 3 and 2 modules wide respectively.
+    /// Returns Result<Pharmacode, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<Pharmacode> {
+        Pharmacode::parse(data.as_ref())
+            .and_then(|d| parse_value(d, &ONE_TRACK_VALUES))
+            .map(|value| Pharmacode { value, narrow: 1, wide: 3, space: 2 })
+    }
+
+    /// Sets the widths of the narrow bars, wide bars and spaces, in modules.
+    /// Returns Error::Ratio if a width is zero or the wide bars are not wider than the narrow bars.
+    pub fn with_widths(mut self, narrow: u8, wide: u8, space: u8) -> Result<Pharmacode> {
+        if narrow == 0 || space == 0 || wide <= narrow {
+            return Err(Error::Ratio);
+        }
+
+        self.narrow = narrow;
+        self.wide = wide;
+        self.space = space;
+
+        Ok(self)
+    }
+
+    /// Returns the bars of the barcode from left to right, where true is a wide bar.
+    fn bars(&self) -> Vec<bool> {
+        let mut bars = vec![];
+        let mut n = self.value;
+
+        while n > 0 {
+            let wide = n % 2 == 0;
+
+            n = if wide { (n - 2) / 2 } else { (n - 1) / 2 };
+            bars.push(wide);
+        }
+
+        bars.reverse();
+        bars
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of encoded binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut enc = vec![];
+
+        for (i, &wide) in self.bars().iter().enumerate() {
+            if i > 0 {
+                enc.extend(vec![0; self.space as usize]);
+            }
+
+            let width = if wide { self.wide } else { self.narrow };
+            enc.extend(vec![1; width as usize]);
+        }
+
+        enc
+    }
+}
+
+impl Parse for Pharmacode {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        1..6
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+impl Barcode for Pharmacode {
+    fn encode(&self) -> Vec<u8> {
+        Pharmacode::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "Pharmacode"
+    }
+
+    fn data(&self) -> String {
+        self.value.to_string()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The tracks occupied by a bar of a Two-track Pharmacode barcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Track {
+    /// A short bar in the top track.
+    Top,
+    /// A short bar in the bottom track.
+    Bottom,
+    /// A full bar across both tracks.
+    Both,
+}
+
+/// The Two-track Pharmacode barcode type.
+#[derive(Copy, Clone, Debug)]
+pub struct PharmacodeTwoTrack(u32);
+
+impl PharmacodeTwoTrack {
+    /// Creates a new barcode.
+    /// Returns Result<PharmacodeTwoTrack, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<PharmacodeTwoTrack> {
+        PharmacodeTwoTrack::parse(data.as_ref())
+            .and_then(|d| parse_value(d, &TWO_TRACK_VALUES))
+            .map(PharmacodeTwoTrack)
+    }
+
+    /// Returns the value encoded in the barcode.
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<Track> of the tracks occupied by each bar, from left to right.
+    pub fn encode(&self) -> Vec<Track> {
+        let mut bars = vec![];
+        let mut n = self.0;
+
+        while n > 0 {
+            let (track, digit) = match n % 3 {
+                0 => (Track::Both, 3),
+                1 => (Track::Bottom, 1),
+                _ => (Track::Top, 2),
+            };
+
+            n = (n - digit) / 3;
+            bars.push(track);
+        }
+
+        bars.reverse();
+        bars
+    }
+
+    /// Returns the layout of the barcode, with the top and bottom tracks as rows of equal height.
+    /// Bars and the spaces between them are each one module wide.
+    pub fn layout(&self) -> Layout {
+        let tracks = self.encode();
+        let row = |top: bool| {
+            let mut modules = vec![];
+
+            for (i, &track) in tracks.iter().enumerate() {
+                if i > 0 {
+                    modules.push(0);
+                }
+
+                let bar = match track {
+                    Track::Both => true,
+                    Track::Top => top,
+                    Track::Bottom => !top,
+                };
+                modules.push(bar as u8);
+            }
+
+            Row::new(1, modules)
+        };
+
+        Layout::new(vec![row(true), row(false)])
+    }
+}
+
+impl Parse for PharmacodeTwoTrack {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        1..8
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+/// Parses the value of the barcode, returning Error::Character if it is out of range.
+fn parse_value(data: &str, values: &RangeInclusive<u32>) -> Result<u32> {
+    match data.parse() {
+        Ok(n) if values.contains(&n) => Ok(n),
+        _ => Err(Error::Character),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::pharmacode::*;
+    use sym::Barcode;
+    use error::Error;
+    use std::char;
+
+    fn collapse_vec(v: Vec<u8>) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn new_pharmacode() {
+        let pharmacode = Pharmacode::new("1234");
+
+        assert!(pharmacode.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_pharmacode() {
+        assert_eq!(Pharmacode::new("12a").err().unwrap(), Error::Character);
+        assert_eq!(Pharmacode::new("2").err().unwrap(), Error::Character);
+        assert_eq!(Pharmacode::new("131071").err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_pharmacode() {
+        let pharmacode = Pharmacode::new("1310700");
+
+        assert_eq!(pharmacode.err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn pharmacode_encode() {
+        let pharmacode1 = Pharmacode::new("3").unwrap();
+        let pharmacode2 = Pharmacode::new("1234").unwrap();
+        let pharmacode3 = Pharmacode::new("131070").unwrap();
+
+        assert_eq!(collapse_vec(pharmacode1.encode()), "1001");
+        assert_eq!(collapse_vec(pharmacode2.encode()), "10010011100111001001110010010011100111");
+        assert_eq!(pharmacode3.encode().len(), 16 * 3 + 15 * 2);
+    }
+
+    #[test]
+    fn pharmacode_widths() {
+        let pharmacode = Pharmacode::new("1234").unwrap().with_widths(2, 5, 1).unwrap();
+
+        assert_eq!(collapse_vec(pharmacode.encode()), "11011011111011111011011111011011011111011111");
+        assert_eq!(Pharmacode::new("1234").unwrap().with_widths(2, 2, 1).err().unwrap(), Error::Ratio);
+        assert_eq!(Pharmacode::new("1234").unwrap().with_widths(1, 3, 0).err().unwrap(), Error::Ratio);
+    }
+
+    #[test]
+    fn pharmacode_as_barcode() {
+        let pharmacode = Pharmacode::new("01234").unwrap();
+
+        assert_eq!(pharmacode.symbology(), "Pharmacode");
+        assert_eq!(pharmacode.checksum(), None);
+        assert_eq!(pharmacode.text(), "1234");
+    }
+
+    #[test]
+    fn new_pharmacode_two_track() {
+        assert_eq!(PharmacodeTwoTrack::new("64570080").unwrap().value(), 64570080);
+        assert_eq!(PharmacodeTwoTrack::new("3").err().unwrap(), Error::Character);
+        assert_eq!(PharmacodeTwoTrack::new("64570081").err().unwrap(), Error::Character);
+        assert_eq!(PharmacodeTwoTrack::new("123456789").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn pharmacode_two_track_encode() {
+        let pharmacode = PharmacodeTwoTrack::new("1234").unwrap();
+
+        assert_eq!(pharmacode.encode(),
+                   vec![Track::Bottom, Track::Bottom, Track::Top, Track::Both, Track::Bottom,
+                        Track::Both, Track::Bottom]);
+    }
+
+    #[test]
+    fn pharmacode_two_track_layout() {
+        let layout = PharmacodeTwoTrack::new("1234").unwrap().layout();
+
+        assert_eq!(layout.rows.len(), 2);
+        assert_eq!(layout.rows[0].height, layout.rows[1].height);
+        assert_eq!(collapse_vec(layout.rows[0].modules.clone()), "0000101000100");
+        assert_eq!(collapse_vec(layout.rows[1].modules.clone()), "1010001010101");
+    }
+}
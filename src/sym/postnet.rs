@@ -0,0 +1,228 @@
+//! Encoders for USPS POSTNET and PLANET barcodes.
+//!
+//! POSTNET (Postal Numeric Encoding Technique) encodes a 5, 9 or 11 digit ZIP code, followed by
+//! a modulo-10 correction digit. PLANET (Postal Alpha Numeric Encoding Technique) encodes a 12 or
+//! 14 digit tracking code, the last digit of which is the correction digit.
+//!
+//! Both are height-modulated symbologies: each digit is encoded as five tall or short bars, two
+//! of which are tall in POSTNET and short in PLANET, between a pair of tall frame bars. Barcodes
+//! are encoded as a sequence of bar `Height`s, which the generators can render via the barcode's
+//! `Layout`.
+
+use sym::Parse;
+use sym::layout::{Height, Layout};
+use error::{Error, Result};
+use std::ops::Range;
+use std::char;
+
+// The POSTNET digit encodings, where true is a tall bar. PLANET uses the inverse encodings.
+const DIGITS: [[bool; 5]; 10] = [
+    [true, true, false, false, false], [false, false, false, true, true],
+    [false, false, true, false, true], [false, false, true, true, false],
+    [false, true, false, false, true], [false, true, false, true, false],
+    [false, true, true, false, false], [true, false, false, false, true],
+    [true, false, false, true, false], [true, false, true, false, false],
+];
+
+/// Calculates the correction digit, which makes the sum of all digits a multiple of 10.
+fn correction_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits.iter().map(|&d| u32::from(d)).sum();
+
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Encodes the given digits and their correction digit between the frame bars.
+fn encode_digits(digits: &[u8], planet: bool) -> Vec<Height> {
+    let mut bars = vec![Height::Tall];
+
+    for &d in digits.iter().chain(Some(correction_digit(digits)).iter()) {
+        for &tall in DIGITS[d as usize].iter() {
+            bars.push(if tall != planet { Height::Tall } else { Height::Short });
+        }
+    }
+
+    bars.push(Height::Tall);
+    bars
+}
+
+/// Converts the given digit characters into their values.
+fn parse_digits(data: &str) -> Vec<u8> {
+    data.chars()
+        .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+        .collect()
+}
+
+/// The POSTNET barcode type.
+#[derive(Debug)]
+pub struct POSTNET(Vec<u8>);
+
+impl POSTNET {
+    /// Creates a new barcode.
+    /// The data is the 5 digit ZIP code, the 9 digit ZIP+4 code or the 11 digit delivery point
+    /// code, without the correction digit.
+    /// Returns Result<POSTNET, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<POSTNET> {
+        POSTNET::parse(data.as_ref()).and_then(|d| match d.len() {
+            5 | 9 | 11 => Ok(POSTNET(parse_digits(d))),
+            _ => Err(Error::Length),
+        })
+    }
+
+    /// Returns the correction digit of the barcode.
+    pub fn check_digit(&self) -> u8 {
+        correction_digit(&self.0[..])
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<Height> of the bars, from left to right, including the frame bars.
+    pub fn encode(&self) -> Vec<Height> {
+        encode_digits(&self.0[..], false)
+    }
+
+    /// Returns the layout of the barcode, with tall bars extending above the short bars.
+    pub fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+impl Parse for POSTNET {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        5..11
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+/// The PLANET barcode type.
+#[derive(Debug)]
+pub struct PLANET(Vec<u8>);
+
+impl PLANET {
+    /// Creates a new barcode.
+    /// The data can either be the 11 or 13 digits without the correction digit, or the full 12
+    /// or 14 digits including the correction digit, in which case the correction digit is
+    /// verified.
+    /// Returns Result<PLANET, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<PLANET> {
+        PLANET::parse(data.as_ref()).and_then(|d| {
+            let mut digits = parse_digits(d);
+            let check = match digits.len() {
+                11 | 13 => None,
+                12 | 14 => digits.pop(),
+                _ => return Err(Error::Length),
+            };
+            let planet = PLANET(digits);
+
+            match check {
+                Some(c) if c != planet.check_digit() => Err(Error::Checksum),
+                _ => Ok(planet),
+            }
+        })
+    }
+
+    /// Returns the correction digit of the barcode.
+    pub fn check_digit(&self) -> u8 {
+        correction_digit(&self.0[..])
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<Height> of the bars, from left to right, including the frame bars.
+    pub fn encode(&self) -> Vec<Height> {
+        encode_digits(&self.0[..], true)
+    }
+
+    /// Returns the layout of the barcode, with tall bars extending above the short bars.
+    pub fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+impl Parse for PLANET {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        11..14
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::postnet::*;
+    use sym::layout::Height;
+    use error::Error;
+
+    fn collapse_heights(v: Vec<Height>) -> String {
+        v.iter().map(|h| h.to_char()).collect()
+    }
+
+    #[test]
+    fn new_postnet() {
+        assert!(POSTNET::new("12345").is_ok());
+        assert!(POSTNET::new("123456789").is_ok());
+        assert!(POSTNET::new("12345678901").is_ok());
+    }
+
+    #[test]
+    fn invalid_data_postnet() {
+        assert_eq!(POSTNET::new("1234A").err().unwrap(), Error::Character);
+        assert_eq!(PLANET::new("4012345235A").err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_postnet() {
+        assert_eq!(POSTNET::new("1234").err().unwrap(), Error::Length);
+        assert_eq!(POSTNET::new("123456").err().unwrap(), Error::Length);
+        assert_eq!(POSTNET::new("123456789012").err().unwrap(), Error::Length);
+        assert_eq!(PLANET::new("4012345235").err().unwrap(), Error::Length);
+        assert_eq!(PLANET::new("401234523563612").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn postnet_check_digit() {
+        assert_eq!(POSTNET::new("12345").unwrap().check_digit(), 5);
+        assert_eq!(POSTNET::new("555551237").unwrap().check_digit(), 2);
+    }
+
+    #[test]
+    fn postnet_encode() {
+        let postnet1 = POSTNET::new("12345").unwrap();
+        let postnet2 = POSTNET::new("555551237").unwrap();
+
+        assert_eq!(collapse_heights(postnet1.encode()), "|...||..|.|..||..|..|.|.|..|.|.|");
+        assert_eq!(collapse_heights(postnet2.encode()), "|.|.|..|.|..|.|..|.|..|.|....||..|.|..||.|...|..|.||");
+    }
+
+    #[test]
+    fn planet_check_digit() {
+        assert_eq!(PLANET::new("40123452356").unwrap().check_digit(), 5);
+        assert!(PLANET::new("401234523565").is_ok());
+        assert_eq!(PLANET::new("401234523566").err().unwrap(), Error::Checksum);
+    }
+
+    #[test]
+    fn planet_encode() {
+        let planet1 = PLANET::new("40123452356").unwrap();
+        let planet2 = PLANET::new("4012345235636").unwrap();
+
+        assert_eq!(collapse_heights(planet1.encode()), "||.||...||||||..||.|.||..||.||.|.|.|||.|.||..||.|.||..|||.|.||");
+        assert_eq!(planet2.encode().len(), 72);
+    }
+
+    #[test]
+    fn postnet_layout() {
+        let layout = POSTNET::new("12345").unwrap().layout();
+
+        assert_eq!(layout.rows.len(), 2);
+        assert_eq!(layout.width(), 32 * 2 - 1);
+        assert_eq!(layout.rows[0].modules[..8], [1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(layout.rows[1].modules[..8], [1, 0, 1, 0, 1, 0, 1, 0]);
+    }
+}
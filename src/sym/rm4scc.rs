@@ -0,0 +1,193 @@
+//! Encoders for Royal Mail 4-State Customer Code (RM4SCC) and Dutch KIX barcodes.
+//!
+//! RM4SCC is a 4-state symbology used by Royal Mail in the UK for postal sorting. It encodes the
+//! postcode and delivery point, followed by a check character, between a start and stop bar.
+//!
+//! KIX (Klant Index) is used by PostNL in the Netherlands. It uses the same characters as
+//! RM4SCC, but has no check character or start and stop bars.
+//!
+//! Each character is encoded as four bars. The character's row in the 6x6 character table
+//! determines which two of the bars have ascenders, and its column which two have descenders.
+
+use sym::Parse;
+use sym::layout::{Layout, State};
+use error::Result;
+use std::ops::Range;
+
+// The characters in order of their position in the character table.
+const CHARS: [char; 36] = [
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H',
+    'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+// The two of four bars that are set for each row (ascenders) or column (descenders).
+const PATTERNS: [[bool; 4]; 6] = [
+    [false, false, true, true], [false, true, false, true], [false, true, true, false],
+    [true, false, false, true], [true, false, true, false], [true, true, false, false],
+];
+
+/// Encodes the given characters as 4-state bars, using the shared character table.
+fn encode_chars(chars: &[char]) -> Vec<State> {
+    let mut bars = vec![];
+
+    for c in chars {
+        let i = CHARS.iter().position(|t| t == c).expect("Unknown character");
+        let (ascenders, descenders) = (PATTERNS[i / 6], PATTERNS[i % 6]);
+
+        for (&a, &d) in ascenders.iter().zip(descenders.iter()) {
+            bars.push(match (a, d) {
+                (true, true) => State::Full,
+                (true, false) => State::Ascender,
+                (false, true) => State::Descender,
+                (false, false) => State::Tracker,
+            });
+        }
+    }
+
+    bars
+}
+
+/// The RM4SCC barcode type.
+#[derive(Debug)]
+pub struct RM4SCC(Vec<char>);
+
+impl RM4SCC {
+    /// Creates a new barcode.
+    /// Returns Result<RM4SCC, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<RM4SCC> {
+        RM4SCC::parse(data.as_ref()).map(|d| RM4SCC(d.chars().collect()))
+    }
+
+    /// Calculates the check character from the sums of the rows and columns of the characters,
+    /// each modulo 6.
+    pub fn check_char(&self) -> char {
+        let (rows, columns) = self.0.iter().fold((0, 0), |(rows, columns), c| {
+            let i = CHARS.iter().position(|t| t == c).expect("Unknown character");
+
+            (rows + i / 6 + 1, columns + i % 6 + 1)
+        });
+        let row = (rows + 5) % 6;
+        let column = (columns + 5) % 6;
+
+        CHARS[row * 6 + column]
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<State> of the bars, from left to right, including the start and stop bars.
+    pub fn encode(&self) -> Vec<State> {
+        let mut chars = self.0.clone();
+        chars.push(self.check_char());
+
+        let mut bars = vec![State::Ascender];
+        bars.extend(encode_chars(&chars[..]));
+        bars.push(State::Full);
+        bars
+    }
+
+    /// Returns the layout of the barcode, with rows for the ascenders, the tracker and the
+    /// descenders.
+    pub fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+impl Parse for RM4SCC {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    /// RM4SCC barcodes are variable-length.
+    fn valid_len() -> Range<u32> {
+        1..50
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        CHARS.to_vec()
+    }
+}
+
+/// The KIX barcode type.
+#[derive(Debug)]
+pub struct KIX(Vec<char>);
+
+impl KIX {
+    /// Creates a new barcode.
+    /// Returns Result<KIX, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<KIX> {
+        KIX::parse(data.as_ref()).map(|d| KIX(d.chars().collect()))
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<State> of the bars, from left to right.
+    pub fn encode(&self) -> Vec<State> {
+        encode_chars(&self.0[..])
+    }
+
+    /// Returns the layout of the barcode, with rows for the ascenders, the tracker and the
+    /// descenders.
+    pub fn layout(&self) -> Layout {
+        Layout::from(&self.encode()[..])
+    }
+}
+
+impl Parse for KIX {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    /// KIX barcodes are variable-length.
+    fn valid_len() -> Range<u32> {
+        1..18
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        CHARS.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::rm4scc::*;
+    use sym::layout::State;
+    use error::Error;
+
+    fn collapse_states(v: Vec<State>) -> String {
+        v.iter().map(|s| s.to_char()).collect()
+    }
+
+    #[test]
+    fn new_rm4scc() {
+        let rm4scc = RM4SCC::new("SN34RD1A");
+
+        assert!(rm4scc.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_rm4scc() {
+        assert_eq!(RM4SCC::new("sn34rd1a").err().unwrap(), Error::Character);
+        assert_eq!(KIX::new("2500GG-30").err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_rm4scc() {
+        assert_eq!(RM4SCC::new("").err().unwrap(), Error::Length);
+        assert_eq!(KIX::new("1234567890ABCDEFGHI").err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn rm4scc_check_char() {
+        assert_eq!(RM4SCC::new("SN34RD1A").unwrap().check_char(), 'K');
+        assert_eq!(RM4SCC::new("0").unwrap().check_char(), '0');
+        assert_eq!(RM4SCC::new("BX11LT1A").unwrap().check_char(), 'I');
+    }
+
+    #[test]
+    fn rm4scc_encode() {
+        let rm4scc = RM4SCC::new("01").unwrap(); // Check character: 8
+
+        assert_eq!(collapse_states(rm4scc.encode()), "ATTFFTDAFTFDAF");
+    }
+
+    #[test]
+    fn kix_encode() {
+        let kix = KIX::new("2500GG30250").unwrap();
+
+        assert_eq!(collapse_states(kix.encode()), "TDFADDAATTFFTTFFDAFTDAFTDTAFTTFFTDFADDAATTFF");
+    }
+}
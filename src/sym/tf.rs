@@ -6,11 +6,24 @@
 //! groups of products (cartons of Cola, etc).
 //!
 //! Most of the time you will want to use the interleaved barcode over the standard option.
+//!
+//! The check digit of interleaved and standard barcodes can be chosen explicitly via
+//! `TF::interleaved_with_check` and `TF::standard_with_check`, which report whether a check digit
+//! was appended and whether the data was padded with a leading zero.
+//!
+//! The Industrial, Matrix, IATA, Datalogic and COOP 2-of-5 variants are also supported. Their
+//! constructors take a `TFVariantCheck` option, as they are never padded, and report the check
+//! digit in the same way.
+//!
+//! ITF-14 barcodes are interleaved 2-of-5 barcodes that encode a GTIN-14 for shipping cartons.
+//! They are usually printed with bearer bars, which are available from `ITF14::layout`.
 
-use sym::Parse;
+use sym::{Barcode, Parse, Ratio};
 use sym::helpers;
-use error::Result;
+use sym::layout::{Layout, Row};
+use error::{Error, Result};
 use std::ops::Range;
+use std::iter::repeat;
 use std::char;
 
 const WIDTHS: [&str; 10] = [
@@ -24,6 +37,32 @@ const ITF_START: [u8; 4] = [1, 0, 1, 0];
 const ITF_STOP: [u8; 4] = [1, 1, 0, 1];
 const STF_START: [u8; 8] = [1, 1, 0, 1, 1, 0, 1, 0];
 const STF_STOP: [u8; 8] = [1, 1, 0, 1, 0, 1, 1, 0];
+const INDUSTRIAL_START: [u8; 10] = [1, 1, 1, 0, 1, 1, 1, 0, 1, 0];
+const INDUSTRIAL_STOP: [u8; 9] = [1, 1, 1, 0, 1, 0, 1, 1, 1];
+const MATRIX_START: [u8; 8] = [1, 1, 1, 0, 1, 0, 1, 0];
+const MATRIX_STOP: [u8; 7] = [1, 1, 1, 0, 1, 0, 1];
+const COOP_START: [u8; 8] = [1, 1, 1, 0, 1, 1, 1, 0];
+const COOP_STOP: [u8; 7] = [1, 0, 0, 0, 1, 1, 1];
+// IATA and Datalogic 2-of-5 share the same start and stop patterns.
+const IATA_START: [u8; 4] = [1, 0, 1, 0];
+const IATA_STOP: [u8; 5] = [1, 1, 1, 0, 1];
+
+// COOP 2-of-5 weights the first four elements 7, 4, 2, 1 rather than 1, 2, 4, 7, so each digit
+// uses the widths of another digit.
+const COOP_WIDTHS: [usize; 10] = [3, 7, 4, 0, 2, 9, 6, 1, 8, 5];
+
+// The element widths of the ITF start and stop patterns.
+const ITF14_START: &str = "NNNN";
+const ITF14_STOP: &str = "WNN";
+
+// The width of the quiet zones on either side of an ITF-14 barcode, in narrow elements.
+const ITF14_QUIET_ZONE: usize = 10;
+// The thickness of ITF-14 bearer bars, in narrow elements. GS1 specifies bearer bars 4.83mm
+// thick, which is just under 5 narrow elements at the nominal 1.016mm narrow element width.
+const ITF14_BEARER_WIDTH: usize = 5;
+// The height of the ITF-14 bars, in narrow elements. GS1 specifies a minimum bar height of
+// 32mm, which is just over 31 narrow elements at the nominal 1.016mm narrow element width.
+const ITF14_BAR_HEIGHT: u32 = 32;
 
 /// The 2-of-5 barcode type.
 #[derive(Debug)]
@@ -32,48 +71,151 @@ pub enum TF {
     Standard(Vec<u8>),
     /// The interleaved 2-of-5 barcode type.
     Interleaved(Vec<u8>),
+    /// The industrial 2-of-5 barcode type.
+    Industrial(Vec<u8>),
+    /// The matrix 2-of-5 barcode type.
+    Matrix(Vec<u8>),
+    /// The IATA 2-of-5 barcode type.
+    IATA(Vec<u8>),
+    /// The Datalogic 2-of-5 barcode type.
+    Datalogic(Vec<u8>),
+    /// The COOP 2-of-5 barcode type.
+    COOP(Vec<u8>),
 }
 
 impl TF {
     /// Creates a new ITF barcode.
     /// If the length of the given data is odd, a checksum value will be computed and appended to
-    /// the data for encoding.
+    /// the data for encoding. Use `TF::interleaved_with_check` to choose the check digit explicitly.
     ///
-    /// Returns Result<TF::Interleaved, Error> indicating parse success.
-    pub fn interleaved<T: AsRef<str>>(data: T) -> Result<TF> {
-        TF::parse(data.as_ref()).and_then(|d| {
-            let mut digits: Vec<u8> = d.chars()
-                                       .map(|c| {
-                                           c.to_digit(10).expect("Unknown character") as u8
-                                       })
-                                       .collect();
-            let checksum_required = digits.len() % 2 == 1;
-
-            if checksum_required {
-                let check_digit = helpers::modulo_10_checksum(&digits[..], false);
-                digits.push(check_digit);
-            }
-
-            Ok(TF::Interleaved(digits))
-        })
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn interleaved<T: AsRef<str>>(data: T) -> Result<CheckedTF> {
+        let check = match data.as_ref().len() % 2 {
+            1 => TFCheck::Always,
+            _ => TFCheck::Never,
+        };
+
+        TF::interleaved_with_check(data, check)
     }
 
     /// Creates a new STF barcode.
     ///
     /// Returns Result<TF::Standard, Error> indicating parse success.
     pub fn standard<T: AsRef<str>>(data: T) -> Result<TF> {
-        TF::parse(data.as_ref()).and_then(|d| {
+        TF::parse(data.as_ref()).map(|d| {
             let digits: Vec<u8> = d.chars()
                                    .map(|c| c.to_digit(10).expect("Unknown character") as u8)
                                    .collect();
-            Ok(TF::Standard(digits))
+            TF::Standard(digits)
+        })
+    }
+
+    /// Creates a new ITF barcode, appending a check digit according to the given option.
+    /// ITF data must have an even number of digits, so it is padded with a leading zero if needed,
+    /// or rejected with Error::Length when using TFCheck::Never.
+    ///
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn interleaved_with_check<T: AsRef<str>>(data: T, check: TFCheck) -> Result<CheckedTF> {
+        TF::digits(data.as_ref(), check == TFCheck::Always).and_then(|mut digits| {
+            let check_digit = match check {
+                TFCheck::Always => digits.last().cloned(),
+                _ => None,
+            };
+            let padded = digits.len() % 2 != 0;
+
+            if padded && check == TFCheck::Never {
+                return Err(Error::Length);
+            }
+
+            if padded {
+                digits.insert(0, 0);
+            }
+
+            Ok(CheckedTF { barcode: TF::Interleaved(digits), check_digit, padded })
+        })
+    }
+
+    /// Creates a new STF barcode, appending a check digit according to the given option.
+    /// STF data can have any number of digits, so it is never padded.
+    ///
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn standard_with_check<T: AsRef<str>>(data: T, check: TFCheck) -> Result<CheckedTF> {
+        TF::unpadded(data.as_ref(), check == TFCheck::Always, TF::Standard)
+    }
+
+    /// Creates a new Industrial 2-of-5 barcode, appending a check digit according to the given
+    /// option.
+    ///
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn industrial<T: AsRef<str>>(data: T, check: TFVariantCheck) -> Result<CheckedTF> {
+        TF::unpadded(data.as_ref(), check == TFVariantCheck::Mod10, TF::Industrial)
+    }
+
+    /// Creates a new Matrix 2-of-5 barcode, appending a check digit according to the given option.
+    ///
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn matrix<T: AsRef<str>>(data: T, check: TFVariantCheck) -> Result<CheckedTF> {
+        TF::unpadded(data.as_ref(), check == TFVariantCheck::Mod10, TF::Matrix)
+    }
+
+    /// Creates a new IATA 2-of-5 barcode, appending a check digit according to the given option.
+    ///
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn iata<T: AsRef<str>>(data: T, check: TFVariantCheck) -> Result<CheckedTF> {
+        TF::unpadded(data.as_ref(), check == TFVariantCheck::Mod10, TF::IATA)
+    }
+
+    /// Creates a new Datalogic 2-of-5 barcode, appending a check digit according to the given
+    /// option.
+    ///
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn datalogic<T: AsRef<str>>(data: T, check: TFVariantCheck) -> Result<CheckedTF> {
+        TF::unpadded(data.as_ref(), check == TFVariantCheck::Mod10, TF::Datalogic)
+    }
+
+    /// Creates a new COOP 2-of-5 barcode, appending a check digit according to the given option.
+    ///
+    /// Returns Result<CheckedTF, Error> indicating parse success.
+    pub fn coop<T: AsRef<str>>(data: T, check: TFVariantCheck) -> Result<CheckedTF> {
+        TF::unpadded(data.as_ref(), check == TFVariantCheck::Mod10, TF::COOP)
+    }
+
+    /// Creates a barcode of a variant that accepts any number of digits, so is never padded.
+    fn unpadded(data: &str, checksum: bool, variant: fn(Vec<u8>) -> TF) -> Result<CheckedTF> {
+        TF::digits(data, checksum).map(|digits| {
+            let check_digit = if checksum { digits.last().cloned() } else { None };
+
+            CheckedTF { barcode: variant(digits), check_digit, padded: false }
+        })
+    }
+
+    /// Parses the digits of the data, appending a modulo-10 check digit if required.
+    /// The rightmost data digit always has a weight of 3.
+    fn digits(data: &str, checksum: bool) -> Result<Vec<u8>> {
+        TF::parse(data).map(|d| {
+            let mut digits: Vec<u8> = d.chars()
+                                       .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                                       .collect();
+
+            if checksum {
+                let even_start = digits.len() % 2 == 0;
+                let check_digit = helpers::modulo_10_checksum(&digits[..], even_start);
+                digits.push(check_digit);
+            }
+
+            digits
         })
     }
 
     fn raw_data(&self) -> &[u8] {
         match *self {
             TF::Standard(ref d) |
-            TF::Interleaved(ref d) => &d[..],
+            TF::Interleaved(ref d) |
+            TF::Industrial(ref d) |
+            TF::Matrix(ref d) |
+            TF::IATA(ref d) |
+            TF::Datalogic(ref d) |
+            TF::COOP(ref d) => &d[..],
         }
     }
 
@@ -109,7 +251,27 @@ impl TF {
     }
 
     fn char_widths(&self, d: u8) -> &'static str {
-        WIDTHS[d as usize]
+        match *self {
+            TF::COOP(_) => WIDTHS[COOP_WIDTHS[d as usize]],
+            _ => WIDTHS[d as usize],
+        }
+    }
+
+    /// Encodes a digit as alternating bars and spaces, followed by a narrow space.
+    fn matrix_char_encoding(&self, d: u8) -> Vec<u8> {
+        let mut encoding = vec![];
+
+        for (i, c) in self.char_widths(d).chars().enumerate() {
+            let module = if i % 2 == 0 { 1 } else { 0 };
+
+            match c {
+                'W' => encoding.extend([module; 3].iter().cloned()),
+                _ => encoding.push(module),
+            }
+        }
+
+        encoding.push(0);
+        encoding
     }
 
     fn stf_payload(&self) -> Vec<u8> {
@@ -122,6 +284,15 @@ impl TF {
         encodings
     }
 
+    fn matrix_payload(&self) -> Vec<u8> {
+        let encodings: Vec<Vec<u8>> = self.raw_data()
+                                          .iter()
+                                          .map(|&d| self.matrix_char_encoding(d))
+                                          .collect();
+
+        helpers::join_iters(encodings.iter())
+    }
+
     fn itf_payload(&self) -> Vec<u8> {
         let weaves: Vec<Vec<u8>> = self.raw_data()
                                        .chunks(2)
@@ -141,8 +312,33 @@ impl TF {
             TF::Interleaved(_) => {
                 helpers::join_slices(&[&ITF_START[..], &self.itf_payload()[..], &ITF_STOP[..]][..])
             }
+            TF::Industrial(_) => {
+                helpers::join_slices(&[&INDUSTRIAL_START[..], &self.stf_payload()[..],
+                                       &INDUSTRIAL_STOP[..]][..])
+            }
+            TF::IATA(_) => {
+                helpers::join_slices(&[&IATA_START[..], &self.stf_payload()[..], &IATA_STOP[..]][..])
+            }
+            TF::Matrix(_) => {
+                helpers::join_slices(&[&MATRIX_START[..], &self.matrix_payload()[..],
+                                       &MATRIX_STOP[..]][..])
+            }
+            TF::Datalogic(_) => {
+                helpers::join_slices(&[&IATA_START[..], &self.matrix_payload()[..],
+                                       &IATA_STOP[..]][..])
+            }
+            TF::COOP(_) => {
+                helpers::join_slices(&[&COOP_START[..], &self.matrix_payload()[..],
+                                       &COOP_STOP[..]][..])
+            }
         }
     }
+
+    /// Encodes the barcode using the given wide-to-narrow ratio (the default is 3:1).
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode_with_ratio(&self, ratio: Ratio) -> Vec<u8> {
+        helpers::apply_ratio(&self.encode()[..], ratio)
+    }
 }
 
 impl Parse for TF {
@@ -158,9 +354,286 @@ impl Parse for TF {
     }
 }
 
+impl Barcode for TF {
+    fn encode(&self) -> Vec<u8> {
+        TF::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        match *self {
+            TF::Standard(_) => "Standard 2-of-5",
+            TF::Interleaved(_) => "Interleaved 2-of-5",
+            TF::Industrial(_) => "Industrial 2-of-5",
+            TF::Matrix(_) => "Matrix 2-of-5",
+            TF::IATA(_) => "IATA 2-of-5",
+            TF::Datalogic(_) => "Datalogic 2-of-5",
+            TF::COOP(_) => "COOP 2-of-5",
+        }
+    }
+
+    // A TF holds no record of whether a check digit was appended, so all of its digits are
+    // reported as data. Constructors that append a check digit return a CheckedTF instead.
+    fn data(&self) -> String {
+        self.raw_data().iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The check digit options of interleaved and standard 2-of-5 barcodes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TFCheck {
+    /// Always append a modulo-10 check digit.
+    Always,
+    /// Never append a check digit.
+    Never,
+    /// Never append a check digit, but pad odd-length ITF data with a leading zero.
+    Pad,
+}
+
+/// The check digit options of the Industrial, Matrix, IATA, Datalogic and COOP 2-of-5 variants,
+/// which accept any number of digits and so are never padded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TFVariantCheck {
+    /// No check digit.
+    None,
+    /// A modulo-10 check digit.
+    Mod10,
+}
+
+/// A 2-of-5 barcode created with an explicit check digit option, along with the changes that
+/// were applied to its data.
+#[derive(Debug)]
+pub struct CheckedTF {
+    barcode: TF,
+    check_digit: Option<u8>,
+    padded: bool,
+}
+
+impl CheckedTF {
+    /// Returns the 2-of-5 barcode.
+    pub fn barcode(&self) -> &TF {
+        &self.barcode
+    }
+
+    /// Returns the check digit appended to the data, if any.
+    pub fn check_digit(&self) -> Option<u8> {
+        self.check_digit
+    }
+
+    /// Returns true if the data was padded with a leading zero.
+    pub fn padded(&self) -> bool {
+        self.padded
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        self.barcode.encode()
+    }
+
+    /// Encodes the barcode using the given wide-to-narrow ratio (the default is 3:1).
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode_with_ratio(&self, ratio: Ratio) -> Vec<u8> {
+        self.barcode.encode_with_ratio(ratio)
+    }
+}
+
+impl Barcode for CheckedTF {
+    fn encode(&self) -> Vec<u8> {
+        CheckedTF::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        self.barcode.symbology()
+    }
+
+    fn data(&self) -> String {
+        let digits = self.barcode.raw_data();
+        let len = digits.len() - self.check_digit.map_or(0, |_| 1);
+
+        digits[..len].iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        self.check_digit.map(|d| d.to_string())
+    }
+}
+
+/// The bearer bars printed around an ITF-14 barcode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Bearer {
+    /// No bearer bars.
+    None,
+    /// Bearer bars above and below the barcode and its quiet zones.
+    TopBottom,
+    /// A bearer bar frame around the barcode and its quiet zones.
+    Frame,
+}
+
+/// The ITF-14 barcode type.
+#[derive(Debug)]
+pub struct ITF14 {
+    digits: Vec<u8>,
+    ratio: Ratio,
+    bearer: Bearer,
+}
+
+impl ITF14 {
+    /// Creates a new barcode with a 3:1 wide-to-narrow ratio and a bearer bar frame.
+    /// The data can either be the 13 digits without the check digit, or the full 14 digits
+    /// including the check digit, in which case the check digit is verified.
+    ///
+    /// Returns Result<ITF14, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<ITF14> {
+        ITF14::parse(data.as_ref()).and_then(|d| {
+            let mut digits: Vec<u8> = d.chars()
+                                       .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+                                       .collect();
+            let check = if digits.len() == 14 { digits.pop() } else { None };
+            let itf14 = ITF14 { digits, ratio: Ratio(6), bearer: Bearer::Frame };
+
+            match check {
+                Some(c) if c != itf14.checksum_digit() => Err(Error::Checksum),
+                _ => Ok(itf14),
+            }
+        })
+    }
+
+    /// Sets the wide-to-narrow ratio.
+    pub fn with_ratio(mut self, ratio: Ratio) -> ITF14 {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the bearer bars.
+    pub fn with_bearer(mut self, bearer: Bearer) -> ITF14 {
+        self.bearer = bearer;
+        self
+    }
+
+    /// Returns the bearer bars that should be printed with the barcode.
+    pub fn bearer(&self) -> Bearer {
+        self.bearer
+    }
+
+    /// Calculates the GS1 check digit using a modulo-10 weighting algorithm.
+    fn checksum_digit(&self) -> u8 {
+        helpers::modulo_10_checksum(&self.digits[..], false)
+    }
+
+    // Encodes the given element widths ('N' or 'W'), which alternate between bars and spaces.
+    fn element_encoding(&self, widths: &str) -> Vec<u8> {
+        let (narrow, wide) = self.ratio.widths();
+        let mut encoding = vec![];
+
+        for (i, w) in widths.chars().enumerate() {
+            let width = if w == 'W' { wide } else { narrow };
+            encoding.extend(repeat(((i + 1) % 2) as u8).take(width));
+        }
+
+        encoding
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let mut digits = self.digits.clone();
+        let mut widths = String::new();
+
+        digits.push(self.checksum_digit());
+
+        for pair in digits.chunks(2) {
+            let bars = WIDTHS[pair[0] as usize].chars();
+            let spaces = WIDTHS[pair[1] as usize].chars();
+
+            for (b, s) in bars.zip(spaces) {
+                widths.push(b);
+                widths.push(s);
+            }
+        }
+
+        self.element_encoding(&widths)
+    }
+
+    /// Encodes the barcode, without bearer bars or quiet zones.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        helpers::join_slices(&[&self.element_encoding(ITF14_START)[..],
+                               &self.payload()[..],
+                               &self.element_encoding(ITF14_STOP)[..]][..])
+    }
+
+    /// Lays out the barcode with its bearer bars, which span the quiet zones on either side of
+    /// the barcode.
+    ///
+    /// The row heights are in narrow elements: the bars are 32 high and the bearer bars are 5
+    /// thick, approximating the GS1 minimum bar height and bearer bar thickness. Bearer bar
+    /// frames are also 5 narrow elements wide at the sides.
+    pub fn layout(&self) -> Layout {
+        let (narrow, _) = self.ratio.widths();
+        let bearer_width = ITF14_BEARER_WIDTH * narrow;
+        let quiet_zone = ITF14_QUIET_ZONE * narrow;
+        let mut bars = self.encode();
+
+        if self.bearer != Bearer::None {
+            bars = [vec![0; quiet_zone], bars, vec![0; quiet_zone]].concat();
+        }
+
+        if self.bearer == Bearer::Frame {
+            bars = [vec![1; bearer_width], bars, vec![1; bearer_width]].concat();
+        }
+
+        match self.bearer {
+            Bearer::None => Layout::from(&bars[..]),
+            _ => {
+                let bearer = Row::new(ITF14_BEARER_WIDTH as u32, vec![1; bars.len()]);
+
+                Layout::new(vec![bearer.clone(), Row::new(ITF14_BAR_HEIGHT, bars), bearer])
+            }
+        }
+    }
+}
+
+impl Parse for ITF14 {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        13..14
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+impl Barcode for ITF14 {
+    fn encode(&self) -> Vec<u8> {
+        ITF14::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "ITF-14"
+    }
+
+    fn data(&self) -> String {
+        self.digits.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum_digit().to_string())
+    }
+
+    fn layout(&self) -> Layout {
+        ITF14::layout(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sym::tf::*;
+    use sym::{Barcode, Ratio};
+    use sym::layout::Row;
     use error::Error;
     use std::char;
 
@@ -171,50 +644,217 @@ mod tests {
 
     #[test]
     fn new_itf() {
-        let itf = TF::interleaved("12345679".to_owned());
+        let itf = TF::interleaved("12345679");
 
         assert!(itf.is_ok());
     }
 
     #[test]
     fn new_stf() {
-        let stf = TF::standard("12345".to_owned());
+        let stf = TF::standard("12345");
 
         assert!(stf.is_ok());
     }
 
     #[test]
     fn invalid_data_itf() {
-        let itf = TF::interleaved("1234er123412".to_owned());
+        let itf = TF::interleaved("1234er123412");
 
         assert_eq!(itf.err().unwrap(), Error::Character);
     }
 
     #[test]
     fn invalid_data_stf() {
-        let stf = TF::standard("WORDUP".to_owned());
+        let stf = TF::standard("WORDUP");
 
         assert_eq!(stf.err().unwrap(), Error::Character);
     }
 
     #[test]
     fn itf_raw_data() {
-        let itf = TF::interleaved("12345679".to_owned()).unwrap();
+        let itf = TF::interleaved("12345679").unwrap();
 
-        assert_eq!(itf.raw_data(), &[1, 2, 3, 4, 5, 6, 7, 9]);
+        assert_eq!(itf.barcode().raw_data(), &[1, 2, 3, 4, 5, 6, 7, 9]);
     }
 
     #[test]
     fn itf_encode() {
-        let itf = TF::interleaved("1234567".to_owned()).unwrap(); // Check digit: 0
+        let itf = TF::interleaved("1234567").unwrap(); // Check digit: 0
 
         assert_eq!(collapse_vec(itf.encode()), "10101110100010101110001110111010001010001110100011100010101010100011100011101101".to_owned());
     }
 
     #[test]
     fn stf_encode() {
-        let stf = TF::standard("1234567".to_owned()).unwrap();
+        let stf = TF::standard("1234567").unwrap();
 
         assert_eq!(collapse_vec(stf.encode()), "110110101110101010111010111010101110111011101010101010111010111011101011101010101110111010101010101110111011010110".to_owned());
     }
+
+    #[test]
+    fn tf_as_barcode() {
+        let itf = TF::interleaved("1234567").unwrap();
+        let stf = TF::standard("1234567").unwrap();
+
+        assert_eq!(itf.symbology(), "Interleaved 2-of-5");
+        assert_eq!(itf.data(), "1234567");
+        assert_eq!(itf.checksum(), Some("0".to_owned()));
+        assert_eq!(itf.text(), "12345670");
+        assert_eq!(TF::interleaved("123456").unwrap().checksum(), None);
+        assert_eq!(stf.symbology(), "Standard 2-of-5");
+        assert_eq!(stf.text(), "1234567");
+    }
+
+    #[test]
+    fn tf_variants_encode() {
+        let industrial = TF::industrial("1234", TFVariantCheck::None).unwrap();
+        let matrix = TF::matrix("1234", TFVariantCheck::None).unwrap();
+        let iata = TF::iata("1234", TFVariantCheck::None).unwrap();
+        let datalogic = TF::datalogic("1234", TFVariantCheck::None).unwrap();
+        let coop = TF::coop("1234", TFVariantCheck::None).unwrap();
+
+        assert_eq!(collapse_vec(industrial.encode()), "111011101011101010101110101110101011101110111010101010101110101110111010111".to_owned());
+        assert_eq!(collapse_vec(matrix.encode()), "1110101011101011101000101110111000101010111011101110101".to_owned());
+        assert_eq!(collapse_vec(iata.encode()), "10101110101010111010111010101110111011101010101010111010111011101".to_owned());
+        assert_eq!(collapse_vec(datalogic.encode()), "1010111010111010001011101110001010101110111011101".to_owned());
+        assert_eq!(collapse_vec(coop.encode()), "1110111010100011101011101110101110001010001011101000111".to_owned());
+    }
+
+    #[test]
+    fn tf_variants_checksum() {
+        let industrial = TF::industrial("1234", TFVariantCheck::Mod10).unwrap(); // Check digit: 8
+        let coop = TF::coop("12345", TFVariantCheck::Mod10).unwrap(); // Check digit: 7
+
+        assert_eq!((industrial.check_digit(), industrial.padded()), (Some(8), false));
+        assert_eq!(industrial.barcode().raw_data(), &[1, 2, 3, 4, 8]);
+        assert_eq!(industrial.data(), "1234");
+        assert_eq!(industrial.checksum(), Some("8".to_owned()));
+        assert_eq!(coop.text(), "123457");
+        assert_eq!(TF::matrix("1234", TFVariantCheck::Mod10).unwrap().check_digit(), Some(8));
+        assert_eq!(TF::iata("1234", TFVariantCheck::Mod10).unwrap().check_digit(), Some(8));
+        assert_eq!(TF::datalogic("1234", TFVariantCheck::Mod10).unwrap().check_digit(), Some(8));
+        assert!(!TF::matrix("123", TFVariantCheck::Mod10).unwrap().padded());
+        assert_eq!(TF::matrix("1234", TFVariantCheck::None).unwrap().checksum(), None);
+        assert_eq!(TF::datalogic("12a4", TFVariantCheck::None).err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn tf_variants_as_barcode() {
+        assert_eq!(TF::industrial("1", TFVariantCheck::None).unwrap().symbology(), "Industrial 2-of-5");
+        assert_eq!(TF::matrix("1", TFVariantCheck::None).unwrap().symbology(), "Matrix 2-of-5");
+        assert_eq!(TF::iata("1", TFVariantCheck::None).unwrap().symbology(), "IATA 2-of-5");
+        assert_eq!(TF::datalogic("1", TFVariantCheck::None).unwrap().symbology(), "Datalogic 2-of-5");
+        assert_eq!(TF::coop("1", TFVariantCheck::None).unwrap().symbology(), "COOP 2-of-5");
+    }
+
+    #[test]
+    fn itf_with_check() {
+        let always_odd = TF::interleaved_with_check("1234567", TFCheck::Always).unwrap();
+        let always_even = TF::interleaved_with_check("123456", TFCheck::Always).unwrap();
+        let never = TF::interleaved_with_check("123456", TFCheck::Never).unwrap();
+        let pad = TF::interleaved_with_check("1234567", TFCheck::Pad).unwrap();
+
+        assert_eq!((always_odd.check_digit(), always_odd.padded()), (Some(0), false));
+        assert_eq!(always_odd.encode(), TF::interleaved("1234567").unwrap().encode());
+        assert_eq!((always_even.check_digit(), always_even.padded()), (Some(5), true));
+        assert_eq!(always_even.barcode().raw_data(), &[0, 1, 2, 3, 4, 5, 6, 5]);
+        assert_eq!((never.check_digit(), never.padded()), (None, false));
+        assert_eq!(never.barcode().raw_data(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!((pad.check_digit(), pad.padded()), (None, true));
+        assert_eq!(pad.barcode().raw_data(), &[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(TF::interleaved_with_check("1234567", TFCheck::Never).err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn stf_with_check() {
+        let always = TF::standard_with_check("1234567", TFCheck::Always).unwrap();
+        let pad = TF::standard_with_check("1234567", TFCheck::Pad).unwrap();
+
+        assert_eq!((always.check_digit(), always.padded()), (Some(0), false));
+        assert_eq!(always.barcode().raw_data(), &[1, 2, 3, 4, 5, 6, 7, 0]);
+        assert_eq!((pad.check_digit(), pad.padded()), (None, false));
+        assert_eq!(pad.encode(), TF::standard("1234567").unwrap().encode());
+    }
+
+    #[test]
+    fn checked_tf_as_barcode() {
+        let itf = TF::interleaved_with_check("123456", TFCheck::Always).unwrap();
+        let stf = TF::standard_with_check("1234567", TFCheck::Never).unwrap();
+
+        assert_eq!(itf.symbology(), "Interleaved 2-of-5");
+        assert_eq!(itf.data(), "0123456");
+        assert_eq!(itf.checksum(), Some("5".to_owned()));
+        assert_eq!(itf.text(), "01234565");
+        assert_eq!(stf.symbology(), "Standard 2-of-5");
+        assert_eq!(stf.checksum(), None);
+        assert_eq!(stf.text(), "1234567");
+    }
+
+    #[test]
+    fn new_itf14() {
+        let itf14_1 = ITF14::new("1540014128876");
+        let itf14_2 = ITF14::new("15400141288763");
+
+        assert!(itf14_1.is_ok());
+        assert!(itf14_2.is_ok());
+    }
+
+    #[test]
+    fn invalid_itf14() {
+        assert_eq!(ITF14::new("154001412887").err().unwrap(), Error::Length);
+        assert_eq!(ITF14::new("154001412887A").err().unwrap(), Error::Character);
+        assert_eq!(ITF14::new("15400141288764").err().unwrap(), Error::Checksum);
+    }
+
+    #[test]
+    fn itf14_encode() {
+        let itf14 = ITF14::new("1540014128876").unwrap(); // Check digit: 3
+        let itf14_2 = ITF14::new("0000000000000").unwrap().with_ratio(Ratio::new(4).unwrap());
+        let itf14_25 = ITF14::new("1540014128876").unwrap().with_ratio(Ratio::new(5).unwrap());
+
+        assert_eq!(collapse_vec(itf14.encode()), "101011100010100010111010101110001000111010001011101110100010001011101011100010001110101000111011101010111000100010001110001110101011101");
+        assert_eq!(collapse_vec(itf14_2.encode()), "1010101011001100101010110011001010101100110010101011001100101010110011001010101100110010101011001100101101");
+        assert_eq!(itf14_25.encode().len(), 241);
+    }
+
+    #[test]
+    fn itf14_layout() {
+        let itf14 = ITF14::new("1540014128876").unwrap();
+        let frame = itf14.layout();
+        let top_bottom = ITF14::new("1540014128876").unwrap().with_bearer(Bearer::TopBottom).layout();
+        let none = ITF14::new("1540014128876").unwrap().with_bearer(Bearer::None).layout();
+
+        assert_eq!(itf14.bearer(), Bearer::Frame);
+        assert_eq!(frame.rows.len(), 3);
+        assert_eq!(frame.width(), 135 + 30);
+        assert_eq!(frame.rows[0], Row::new(5, vec![1; 165]));
+        assert_eq!(frame.rows[1].modules[..15], [1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(frame.rows[1].modules[15..150], itf14.encode()[..]);
+        assert_eq!(top_bottom.width(), 135 + 20);
+        assert_eq!(top_bottom.rows[0], Row::new(5, vec![1; 155]));
+        assert_eq!(top_bottom.rows[1].height, 32);
+        assert_eq!(top_bottom.rows[1].modules[..10], [0; 10]);
+        assert_eq!(top_bottom.rows[1].modules[10..145], itf14.encode()[..]);
+        assert_eq!(top_bottom.rows[2], top_bottom.rows[0]);
+        assert_eq!(none.rows, vec![Row::new(1, itf14.encode())]);
+    }
+
+    #[test]
+    fn itf14_as_barcode() {
+        let itf14 = ITF14::new("1540014128876").unwrap();
+
+        assert_eq!(itf14.symbology(), "ITF-14");
+        assert_eq!(itf14.checksum(), Some("3".to_owned()));
+        assert_eq!(itf14.text(), "15400141288763");
+    }
+
+    #[test]
+    fn tf_encode_with_ratio() {
+        let itf = TF::interleaved("12").unwrap();
+        let stf = TF::standard("1").unwrap();
+
+        assert_eq!(collapse_vec(itf.encode_with_ratio(Ratio::new(4).unwrap())), "1010110100101011001101");
+        assert_eq!(collapse_vec(itf.encode_with_ratio(Ratio::new(6).unwrap())), "101011101000101011100011101");
+        assert_eq!(collapse_vec(stf.encode_with_ratio(Ratio::new(4).unwrap())), "1101101011010101011011010110");
+    }
 }
@@ -0,0 +1,287 @@
+//! Encoder for UPC-E barcodes.
+//!
+//! UPC-E barcodes are zero-suppressed UPC-A barcodes, used on small retail packages where there
+//! is not enough room for a full UPC-A. Only number systems 0 and 1 can be encoded.
+//!
+//! Data can be provided in any of the following forms:
+//!   * 6 digits (number system 0 is assumed).
+//!   * 7 digits (number system followed by the 6 digits).
+//!   * 8 digits (number system, the 6 digits and the check digit).
+//!   * A 12 digit UPC-A (including the check digit), which is compressed to UPC-E.
+
+use sym::{Barcode, Parse, helpers};
+use sym::ean_supp::WithAddOn;
+use error::{Error, Result};
+use sym::ean13::{ENCODINGS, LEFT_GUARD};
+use std::ops::Range;
+use std::char;
+
+/// Maps parity (odd/even) for the six digits based on the check digit for
+/// number system 0. Number system 1 uses the inverse mapping.
+const PARITY: [[usize; 6]; 10] = [
+    [1,1,1,0,0,0], [1,1,0,1,0,0], [1,1,0,0,1,0],
+    [1,1,0,0,0,1], [1,0,1,1,0,0], [1,0,0,1,1,0],
+    [1,0,0,0,1,1], [1,0,1,0,1,0], [1,0,1,0,0,1],
+    [1,0,0,1,0,1],
+];
+
+/// The right-hand guard pattern.
+pub const RIGHT_GUARD: [u8; 6] = [0, 1, 0, 1, 0, 1];
+
+/// The UPC-E barcode type.
+#[derive(Debug)]
+pub struct UPCE(Vec<u8>);
+
+impl UPCE {
+    /// Creates a new barcode.
+    /// Returns Result<UPCE, Error> indicating parse success.
+    pub fn new<T: AsRef<str>>(data: T) -> Result<UPCE> {
+        let digits: Vec<u8> = UPCE::parse(data.as_ref())?
+            .chars()
+            .map(|c| c.to_digit(10).expect("Unknown character") as u8)
+            .collect();
+
+        let (digits, check) = match digits.len() {
+            6 => ([&[0], &digits[..]].concat(), None),
+            7 => (digits, None),
+            8 => (digits[..7].to_vec(), Some(digits[7])),
+            12 => (UPCE::compress(&digits[..11])?, Some(digits[11])),
+            _ => return Err(Error::Length),
+        };
+
+        if digits[0] > 1 {
+            return Err(Error::Character);
+        }
+
+        let upce = UPCE(digits);
+
+        match check {
+            Some(c) if c != upce.checksum_digit() => Err(Error::Checksum),
+            _ => Ok(upce),
+        }
+    }
+
+    /// Creates a new barcode with an attached EAN-2 or EAN-5 add-on.
+    /// Returns Result<WithAddOn<UPCE>, Error> indicating parse success.
+    pub fn with_addon<T: AsRef<str>, U: AsRef<str>>(data: T, addon: U) -> Result<WithAddOn<UPCE>> {
+        UPCE::new(data).and_then(|b| WithAddOn::new(b, addon))
+    }
+
+    /// Compresses the first 11 digits of a UPC-A into the number system and 6 digits of a UPC-E
+    /// by applying the zero-suppression rules.
+    fn compress(upca: &[u8]) -> Result<Vec<u8>> {
+        let (ns, m, p) = (upca[0], &upca[1..6], &upca[6..11]);
+
+        let digits = if m[2] <= 2 && m[3..] == [0, 0] && p[..2] == [0, 0] {
+            [m[0], m[1], p[2], p[3], p[4], m[2]]
+        } else if m[3..] == [0, 0] && p[..3] == [0, 0, 0] {
+            [m[0], m[1], m[2], p[3], p[4], 3]
+        } else if m[4] == 0 && p[..4] == [0, 0, 0, 0] {
+            [m[0], m[1], m[2], m[3], p[4], 4]
+        } else if p[..4] == [0, 0, 0, 0] && p[4] >= 5 {
+            [m[0], m[1], m[2], m[3], m[4], p[4]]
+        } else {
+            return Err(Error::Character);
+        };
+
+        Ok([&[ns], &digits[..]].concat())
+    }
+
+    /// Expands the barcode into the first 11 digits of the equivalent UPC-A.
+    fn expand(&self) -> Vec<u8> {
+        let d = &self.0[1..];
+
+        let manufacturer_product = match d[5] {
+            0..=2 => [d[0], d[1], d[5], 0, 0, 0, 0, d[2], d[3], d[4]],
+            3 => [d[0], d[1], d[2], 0, 0, 0, 0, 0, d[3], d[4]],
+            4 => [d[0], d[1], d[2], d[3], 0, 0, 0, 0, 0, d[4]],
+            _ => [d[0], d[1], d[2], d[3], d[4], 0, 0, 0, 0, d[5]],
+        };
+
+        [&self.0[..1], &manufacturer_product[..]].concat()
+    }
+
+    /// Returns the equivalent 12 digit UPC-A, including the check digit.
+    pub fn to_upca(&self) -> String {
+        self.expand()
+            .iter()
+            .chain(Some(self.checksum_digit()).iter())
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    /// Calculates the checksum digit of the equivalent UPC-A using a modulo-10 weighting
+    /// algorithm.
+    fn checksum_digit(&self) -> u8 {
+        helpers::modulo_10_checksum(&self.expand()[..], false)
+    }
+
+    fn number_system_digit(&self) -> u8 {
+        self.0[0]
+    }
+
+    fn char_encoding(&self, side: usize, d: u8) -> [u8; 7] {
+        ENCODINGS[side][d as usize]
+    }
+
+    fn payload_digits(&self) -> &[u8] {
+        &self.0[1..]
+    }
+
+    fn parity_mapping(&self) -> [usize; 6] {
+        let mut parity = PARITY[self.checksum_digit() as usize];
+
+        if self.number_system_digit() == 1 {
+            for p in parity.iter_mut() {
+                *p = 1 - *p;
+            }
+        }
+
+        parity
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        let slices: Vec<[u8; 7]> = self.payload_digits()
+                                       .iter()
+                                       .zip(self.parity_mapping().iter())
+                                       .map(|(d, s)| self.char_encoding(*s, *d))
+                                       .collect();
+
+        helpers::join_iters(slices.iter())
+    }
+
+    /// Encodes the barcode.
+    /// Returns a Vec<u8> of binary digits.
+    pub fn encode(&self) -> Vec<u8> {
+        helpers::join_slices(&[&LEFT_GUARD[..],
+                               &self.payload()[..],
+                               &RIGHT_GUARD[..]][..])
+    }
+}
+
+impl Parse for UPCE {
+    /// Returns the valid length of data acceptable in this type of barcode.
+    fn valid_len() -> Range<u32> {
+        6..12
+    }
+
+    /// Returns the set of valid characters allowed in this type of barcode.
+    fn valid_chars() -> Vec<char> {
+        (0..10).map(|i| char::from_digit(i, 10).unwrap()).collect()
+    }
+}
+
+impl Barcode for UPCE {
+    fn encode(&self) -> Vec<u8> {
+        UPCE::encode(self)
+    }
+
+    fn symbology(&self) -> &'static str {
+        "UPC-E"
+    }
+
+    fn data(&self) -> String {
+        self.0.iter().map(|d| d.to_string()).collect()
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum_digit().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sym::upce::*;
+    use sym::Barcode;
+    use error::Error;
+    use std::char;
+
+    fn collapse_vec(v: Vec<u8>) -> String {
+        let chars = v.iter().map(|d| char::from_digit(*d as u32, 10).unwrap());
+        chars.collect()
+    }
+
+    #[test]
+    fn new_upce() {
+        let upce1 = UPCE::new("425261");
+        let upce2 = UPCE::new("0425261");
+        let upce3 = UPCE::new("04252614");
+        let upce4 = UPCE::new("042100005264");
+
+        assert!(upce1.is_ok());
+        assert!(upce2.is_ok());
+        assert!(upce3.is_ok());
+        assert!(upce4.is_ok());
+    }
+
+    #[test]
+    fn invalid_data_upce() {
+        let upce1 = UPCE::new("42e261");
+        let upce2 = UPCE::new("2425261");
+        let upce3 = UPCE::new("012345678905");
+
+        assert_eq!(upce1.err().unwrap(), Error::Character);
+        assert_eq!(upce2.err().unwrap(), Error::Character);
+        assert_eq!(upce3.err().unwrap(), Error::Character);
+    }
+
+    #[test]
+    fn invalid_len_upce() {
+        let upce1 = UPCE::new("42526");
+        let upce2 = UPCE::new("042526141");
+
+        assert_eq!(upce1.err().unwrap(), Error::Length);
+        assert_eq!(upce2.err().unwrap(), Error::Length);
+    }
+
+    #[test]
+    fn invalid_checksum_upce() {
+        let upce1 = UPCE::new("04252615");
+        let upce2 = UPCE::new("042100005265");
+
+        assert_eq!(upce1.err().unwrap(), Error::Checksum);
+        assert_eq!(upce2.err().unwrap(), Error::Checksum);
+    }
+
+    #[test]
+    fn upce_compress() {
+        let upce1 = UPCE::new("042100005264").unwrap();
+        let upce2 = UPCE::new("012300000642").unwrap();
+        let upce3 = UPCE::new("012340000060").unwrap();
+        let upce4 = UPCE::new("123456000056").unwrap();
+
+        assert_eq!(upce1.data(), "0425261");
+        assert_eq!(upce2.data(), "0123643");
+        assert_eq!(upce3.data(), "0123464");
+        assert_eq!(upce4.data(), "1234565");
+    }
+
+    #[test]
+    fn upce_expand() {
+        let upce1 = UPCE::new("425261").unwrap();
+        let upce2 = UPCE::new("0123643").unwrap();
+        let upce3 = UPCE::new("1234565").unwrap();
+
+        assert_eq!(upce1.to_upca(), "042100005264");
+        assert_eq!(upce2.to_upca(), "012300000642");
+        assert_eq!(upce3.to_upca(), "123456000056");
+    }
+
+    #[test]
+    fn upce_encode() {
+        let upce1 = UPCE::new("0425261").unwrap(); // Check digit: 4
+        let upce2 = UPCE::new("1234565").unwrap(); // Check digit: 6
+
+        assert_eq!(collapse_vec(upce1.encode()), "101001110100100110111001001101101011110011001010101");
+        assert_eq!(collapse_vec(upce2.encode()), "101001001101000010011101011100101011110110001010101");
+    }
+
+    #[test]
+    fn upce_as_barcode() {
+        let upce = UPCE::new("042100005264").unwrap();
+
+        assert_eq!(upce.symbology(), "UPC-E");
+        assert_eq!(upce.checksum(), Some("4".to_owned()));
+        assert_eq!(upce.text(), "04252614");
+    }
+}